@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use tekken::simulation::broad_phase::{sweep_and_prune, Aabb};
+use tekken::simulation::collision::swept_overlaps;
+use tekken::simulation::{Shape, SimRng, ARENA_DEPTH, ARENA_WIDTH};
+
+struct Body {
+    shape: Shape,
+    from: Transform,
+    to: Transform,
+}
+
+impl Body {
+    fn bounds(&self) -> Aabb {
+        Aabb::swept(&self.shape, &self.from, &self.to)
+    }
+
+    fn hits(&self, other: &Body) -> bool {
+        swept_overlaps(
+            &self.shape,
+            &self.from,
+            &self.to,
+            &other.shape,
+            &other.from,
+            &other.to,
+        )
+    }
+}
+
+fn scatter(rng: &mut SimRng, count: usize, shape: Shape) -> Vec<Body> {
+    (0..count)
+        .map(|_| {
+            let from = Transform::from_xyz(
+                rng.next_f32() * ARENA_WIDTH,
+                rng.next_f32() * 150.,
+                (rng.next_f32() - 0.5) * ARENA_DEPTH,
+            )
+            .with_rotation(Quat::from_rotation_y(
+                rng.next_f32() * std::f32::consts::TAU,
+            ));
+            let mut to = from;
+            to.translation.x += (rng.next_f32() - 0.5) * 20.;
+            Body { shape, from, to }
+        })
+        .collect()
+}
+
+fn brute_force(hitboxes: &[Body], fighters: &[Body]) -> Vec<(usize, usize)> {
+    let mut hits = Vec::new();
+    for (i, hitbox) in hitboxes.iter().enumerate() {
+        for (j, fighter) in fighters.iter().enumerate() {
+            if hitbox.hits(fighter) {
+                hits.push((i, j));
+            }
+        }
+    }
+    hits
+}
+
+fn with_broad_phase(hitboxes: &[Body], fighters: &[Body]) -> Vec<(usize, usize)> {
+    let a: Vec<Aabb> = hitboxes.iter().map(Body::bounds).collect();
+    let b: Vec<Aabb> = fighters.iter().map(Body::bounds).collect();
+    sweep_and_prune(&a, &b)
+        .into_iter()
+        .filter(|&(i, j)| hitboxes[i].hits(&fighters[j]))
+        .collect()
+}
+
+fn hit_detection(c: &mut Criterion) {
+    let hitbox = Shape::Box {
+        half_extents: Vec3::new(30., 15., 15.),
+    };
+    let hurtbox = Shape::Capsule {
+        radius: 30.,
+        half_height: 45.,
+    };
+    let mut group = c.benchmark_group("hit_detection");
+    for (hitboxes, fighters) in [(2, 2), (100, 10), (500, 50), (2000, 100)] {
+        let mut rng = SimRng::new(7);
+        let a = scatter(&mut rng, hitboxes, hitbox);
+        let b = scatter(&mut rng, fighters, hurtbox);
+        assert_eq!(brute_force(&a, &b), with_broad_phase(&a, &b));
+        let label = format!("{hitboxes}x{fighters}");
+        group.bench_with_input(BenchmarkId::new("brute_force", &label), &(), |bench, _| {
+            bench.iter(|| brute_force(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("sweep_and_prune", &label),
+            &(),
+            |bench, _| bench.iter(|| with_broad_phase(black_box(&a), black_box(&b))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hit_detection);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use tekken::simulation::{
+    Action, FighterInput, MatchScore, Player, RoundState, SimFrame, Stage, DT,
+};
+use tekken::SimulationPlugin;
+
+const MAX_FRAMES: u64 = 60 * 60 * 5;
+
+fn main() {
+    let mut app = App::new();
+    // The scripted fighter only walks straight ahead, so fight where no block is in the way.
+    app.add_plugins((MinimalPlugins, SimulationPlugin))
+        .insert_resource(Stage::Void)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            DT,
+        )))
+        .add_systems(Update, scripted_input_system);
+    app.finish();
+    app.cleanup();
+
+    while app.world.resource::<SimFrame>().0 < MAX_FRAMES {
+        app.update();
+        if *app.world.resource::<State<RoundState>>().get() == RoundState::MatchOver {
+            break;
+        }
+    }
+
+    let frame = app.world.resource::<SimFrame>().0;
+    let score = app.world.resource::<MatchScore>();
+    println!(
+        "frame {frame}: round {} score {} - {}",
+        score.round, score.wins[0], score.wins[1]
+    );
+}
+
+fn scripted_input_system(frame: Res<SimFrame>, mut fighters: Query<(&Player, &mut FighterInput)>) {
+    for (player, mut input) in fighters.iter_mut() {
+        if player.id != 1 {
+            continue;
+        }
+        input.held = 1 << Action::Right as u8;
+        if frame.0.is_multiple_of(20) {
+            input.pressed |= 1 << Action::Punch as u8;
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use clap::Parser;
+use std::path::PathBuf;
+use tekken::simulation::cpu::MAX_CPU_LEVEL;
+use tekken::simulation::{MatchConfig, Stage};
+
+#[derive(Parser, Debug)]
+#[command(name = "tekken", about = "Mini Tekken - Bevy")]
+pub struct Cli {
+    /// Let the CPU control Player 2 at the given difficulty (1-9)
+    #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(u8).range(1..=MAX_CPU_LEVEL as i64))]
+    pub p2_cpu: Option<u8>,
+
+    /// Stage to fight on (dojo, sunset, void)
+    #[arg(long, default_value = "dojo")]
+    pub stage: Stage,
+
+    /// Play back a recorded replay instead of live input
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+
+    /// Record both players' inputs to a replay file
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Run the simulation without a window
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Number of frames to simulate in headless mode, runs until the match ends if omitted
+    #[arg(long, value_name = "N", requires = "headless")]
+    pub frames: Option<u64>,
+
+    /// Seed for the simulation's random number generator
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Window resolution, e.g. 1280x720
+    #[arg(long, value_name = "WxH", value_parser = parse_resolution)]
+    pub windowed: Option<(f32, f32)>,
+
+    /// Load match rules from a RON file before applying the flags below
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Starting health of each fighter
+    #[arg(long, value_name = "HP")]
+    pub health: Option<f32>,
+
+    /// Number of rounds in the match
+    #[arg(long)]
+    pub rounds: Option<u32>,
+
+    /// Round length in seconds, 0 for no time limit
+    #[arg(long, value_name = "SECS")]
+    pub round_time: Option<u32>,
+
+    /// Multiplier applied to all damage
+    #[arg(long, value_name = "MULT")]
+    pub damage: Option<f32>,
+
+    /// Starting health multiplier for a player, e.g. 2=0.8
+    #[arg(long, value_name = "PLAYER=MULT", value_parser = parse_handicap)]
+    pub handicap: Vec<(usize, f32)>,
+
+    /// Let chip damage from blocked hits KO a fighter
+    #[arg(long)]
+    pub chip_ko: bool,
+
+    /// Stop low attacks from hitting knocked-down fighters
+    #[arg(long)]
+    pub no_otg: bool,
+}
+
+impl Cli {
+    pub fn match_config(&self, defaults: &MatchConfig) -> Result<MatchConfig, String> {
+        let mut config = match &self.config {
+            Some(path) => MatchConfig::load(path)?,
+            None => defaults.clone(),
+        };
+        if let Some(health) = self.health {
+            config.starting_health = health;
+        }
+        if let Some(rounds) = self.rounds {
+            config.rounds = rounds;
+        }
+        if let Some(round_time) = self.round_time {
+            config.round_time = round_time;
+        }
+        if let Some(damage) = self.damage {
+            config.damage_multiplier = damage;
+        }
+        for &(player, mult) in &self.handicap {
+            config.handicap[player - 1] = mult;
+        }
+        if self.chip_ko {
+            config.chip_ko = true;
+        }
+        if self.no_otg {
+            config.otg = false;
+        }
+        Ok(config)
+    }
+}
+
+fn parse_resolution(raw: &str) -> Result<(f32, f32), String> {
+    let (w, h) = raw
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WxH, got {raw}"))?;
+    let parse = |v: &str| {
+        v.parse::<u32>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| format!("invalid resolution {raw}"))
+    };
+    Ok((parse(w)? as f32, parse(h)? as f32))
+}
+
+fn parse_handicap(raw: &str) -> Result<(usize, f32), String> {
+    let (player, mult) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected PLAYER=MULT, got {raw}"))?;
+    let player: usize = player
+        .parse()
+        .map_err(|_| format!("invalid player {player}"))?;
+    if !(1..=2).contains(&player) {
+        return Err(format!("unknown player {player}"));
+    }
+    let mult = mult
+        .parse()
+        .map_err(|_| format!("invalid multiplier {mult}"))?;
+    Ok((player, mult))
+}
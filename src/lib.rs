@@ -0,0 +1,7 @@
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
+pub mod presentation;
+pub mod simulation;
+
+pub use presentation::PresentationPlugin;
+pub use simulation::SimulationPlugin;
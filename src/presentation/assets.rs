@@ -0,0 +1,180 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::simulation::{Fighter, FighterSource, GameSet};
+
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AssetLoadState {
+    #[default]
+    Loading,
+    Ready,
+}
+
+#[derive(Resource)]
+pub struct FighterAssets {
+    /// Loaded fighter models by path; fighters whose model is missing use `body_mesh`.
+    pub scenes: HashMap<String, Handle<Scene>>,
+    pub body_mesh: Handle<Mesh>,
+    pub body_material: Handle<StandardMaterial>,
+    pub healthbar_mesh: Handle<Mesh>,
+    pub healthbar_material: Handle<StandardMaterial>,
+    pub hitbox_mesh: Handle<Mesh>,
+    pub hitbox_material: Handle<StandardMaterial>,
+}
+
+#[derive(Component)]
+pub struct LoadingText;
+
+#[derive(Component)]
+pub struct AssetErrorText;
+
+pub struct AssetLoadingPlugin;
+
+impl Plugin for AssetLoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AssetLoadState>()
+            .add_systems(Startup, load_fighter_assets)
+            .add_systems(
+                Update,
+                check_asset_load_system
+                    .run_if(in_state(AssetLoadState::Loading))
+                    .in_set(GameSet::Presentation),
+            )
+            .add_systems(OnEnter(AssetLoadState::Ready), finish_loading_system);
+    }
+}
+
+fn load_fighter_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut time: ResMut<Time>,
+) {
+    commands.insert_resource(FighterAssets {
+        scenes: HashMap::new(),
+        body_mesh: meshes.add(Mesh::from(shape::Cube { size: 80.0 })),
+        body_material: materials.add(StandardMaterial {
+            base_color: Color::rgb(0.8, 0.2, 0.2),
+            ..default()
+        }),
+        healthbar_mesh: meshes.add(Mesh::from(shape::Box::new(60.0, 6.0, 1.0))),
+        healthbar_material: materials.add(StandardMaterial {
+            base_color: Color::GREEN,
+            emissive: Color::GREEN,
+            ..default()
+        }),
+        hitbox_mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        hitbox_material: materials.add(StandardMaterial {
+            base_color: Color::rgba(1.0, 0.0, 0.0, 0.5),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+    });
+
+    // Hold the fixed-step simulation until the fighters can be drawn.
+    time.pause();
+
+    commands.spawn((
+        TextBundle::from_section(
+            "LOADING...",
+            TextStyle {
+                font_size: 40.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            right: Val::Px(20.0),
+            ..default()
+        }),
+        LoadingText,
+    ));
+}
+
+fn check_asset_load_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut fighter_assets: ResMut<FighterAssets>,
+    sources: Query<&FighterSource>,
+    fighters: Query<&Fighter>,
+    mut next_state: ResMut<NextState<AssetLoadState>>,
+) {
+    let is_pending = |state| matches!(state, LoadState::NotLoaded | LoadState::Loading);
+    if sources
+        .iter()
+        .any(|source| is_pending(asset_server.get_load_state(&source.0)))
+    {
+        return;
+    }
+    // Definitions have settled, so every fighter now names the model it wants.
+    for model in fighters.iter().filter_map(|fighter| fighter.model.as_ref()) {
+        if !fighter_assets.scenes.contains_key(model) {
+            let handle = asset_server.load(model.as_str());
+            fighter_assets.scenes.insert(model.clone(), handle);
+        }
+    }
+    if fighter_assets
+        .scenes
+        .values()
+        .any(|scene| is_pending(asset_server.get_load_state(scene)))
+    {
+        return;
+    }
+
+    let mut failures = Vec::new();
+    for source in sources.iter() {
+        if asset_server.get_load_state(&source.0) != LoadState::Loaded {
+            let path = asset_server.get_handle_path(&source.0);
+            failures.push(format!(
+                "Failed to load fighter {}, using the default fighter",
+                path.map_or("?".to_string(), |path| path.path().display().to_string())
+            ));
+        }
+    }
+    fighter_assets.scenes.retain(|model, scene| {
+        let loaded = asset_server.get_load_state(&*scene) == LoadState::Loaded;
+        if !loaded {
+            failures.push(format!(
+                "Failed to load {model}, using placeholder fighters"
+            ));
+        }
+        loaded
+    });
+    failures.dedup();
+
+    for (line, failure) in failures.into_iter().enumerate() {
+        error!("{failure}");
+        commands.spawn((
+            TextBundle::from_section(
+                failure,
+                TextStyle {
+                    font_size: 20.0,
+                    color: Color::rgb(1.0, 0.3, 0.3),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0 + 24.0 * line as f32),
+                left: Val::Px(10.0),
+                ..default()
+            }),
+            AssetErrorText,
+        ));
+    }
+    next_state.set(AssetLoadState::Ready);
+}
+
+fn finish_loading_system(
+    mut commands: Commands,
+    mut time: ResMut<Time>,
+    loading_text: Query<Entity, With<LoadingText>>,
+) {
+    time.unpause();
+    for entity in loading_text.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::ecs::system::ParamSet;
+use bevy::prelude::*;
+
+use crate::simulation::{GameEntity, GameSet, Player, ARENA_WIDTH};
+
+#[derive(Component)]
+pub struct MainCamera;
+
+pub struct CameraRigPlugin;
+
+impl Plugin for CameraRigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(Update, camera_follow_system.in_set(GameSet::Presentation));
+    }
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera3dBundle {
+            camera: Camera {
+                order: 0,
+                ..default()
+            },
+            transform: Transform::from_xyz(ARENA_WIDTH / 2., 300., 600.)
+                .looking_at(Vec3::new(ARENA_WIDTH / 2., 0., 0.), Vec3::Y),
+            ..default()
+        },
+        MainCamera,
+        GameEntity,
+    ));
+}
+
+fn camera_follow_system(
+    time: Res<Time>,
+    mut params: ParamSet<(
+        Query<&mut Transform, With<MainCamera>>,
+        Query<&Transform, With<Player>>,
+    )>,
+) {
+    let players: Vec<Vec3> = params.p1().iter().map(|t| t.translation).collect();
+    if players.len() < 2 {
+        return;
+    }
+    let p1 = players[0];
+    let p2 = players[1];
+    let midpoint = (p1 + p2) / 2.0;
+    let mut fight_axis = p2 - p1;
+    fight_axis.y = 0.0;
+    if fight_axis.length_squared() < 0.001 {
+        fight_axis = Vec3::X;
+    } else {
+        fight_axis = fight_axis.normalize();
+    }
+    let base_distance = 500.0;
+    let zoom_factor = 15.0;
+    let fixed_height = 200.0;
+    let distance_between = (p2 - p1).length();
+    let zoomed_distance = base_distance + zoom_factor * distance_between.sqrt();
+    let cam_dir = Vec3::new(-fight_axis.z, 0.0, fight_axis.x);
+    let target_pos = midpoint + cam_dir * zoomed_distance + Vec3::Y * fixed_height;
+    for mut cam_transform in params.p0().iter_mut() {
+        cam_transform.translation = cam_transform
+            .translation
+            .lerp(target_pos, 5.0 * time.delta_seconds());
+        cam_transform.look_at(midpoint, Vec3::Y);
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use super::settings::Settings;
+use crate::simulation::{CpuController, FighterInput, GameSet, MatchScore, Player, RoundState};
+
+pub struct KeyboardControlsPlugin;
+
+impl Plugin for KeyboardControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                keyboard_input_system,
+                match_over_system.run_if(in_state(RoundState::MatchOver)),
+            )
+                .in_set(GameSet::Input),
+        );
+    }
+}
+
+fn keyboard_input_system(
+    keyboard: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    round: Res<State<RoundState>>,
+    mut query: Query<(&Player, &mut FighterInput), Without<CpuController>>,
+) {
+    // Presses are only consumed while fighting; anything earlier would fire on the first tick.
+    let fighting = *round.get() == RoundState::Fighting;
+    for (player, mut input) in query.iter_mut() {
+        let Some(bindings) = settings.controls.player(player.id) else {
+            continue;
+        };
+        let mut held = 0;
+        let mut pressed = 0;
+        for (action, key) in bindings.iter() {
+            if keyboard.pressed(key) {
+                held |= 1 << action as u8;
+            }
+            if keyboard.just_pressed(key) {
+                pressed |= 1 << action as u8;
+            }
+        }
+        input.held = held;
+        if fighting {
+            input.pressed |= pressed;
+        }
+    }
+}
+
+fn match_over_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut score: ResMut<MatchScore>,
+    mut next_state: ResMut<NextState<RoundState>>,
+) {
+    if keyboard.just_pressed(KeyCode::Return) {
+        *score = MatchScore::default();
+        next_state.set(RoundState::RoundIntro);
+    }
+}
@@ -0,0 +1,330 @@
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::prelude::*;
+
+use crate::simulation::{
+    Combo, GameSet, Health, MatchConfig, MatchScore, Player, RoundClock, RoundState, TICK_RATE,
+};
+
+/// Seconds the combo counter takes to fade out after a combo drops.
+const COMBO_FADE_SECS: f32 = 1.0;
+
+#[derive(Component)]
+pub struct UiHealthBar;
+
+#[derive(Component)]
+pub struct PlayerHealthBar {
+    pub player_id: usize,
+}
+
+/// "N HITS" counter shown under a player's health bar while their combo lasts.
+#[derive(Component)]
+pub struct ComboCounter {
+    pub player_id: usize,
+}
+
+#[derive(Component)]
+pub struct RoundBanner;
+
+#[derive(Component)]
+pub struct RoundClockText;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_ui).add_systems(
+            Update,
+            (
+                update_healthbar_ui_system,
+                combo_counter_system,
+                round_clock_text_system,
+                round_banner_system.run_if(state_changed::<RoundState>()),
+            )
+                .in_set(GameSet::Presentation),
+        );
+    }
+}
+
+fn setup_ui(mut commands: Commands) {
+    commands.spawn(Camera2dBundle {
+        camera: Camera {
+            order: 1,
+            ..default()
+        },
+        camera_2d: Camera2d {
+            clear_color: ClearColorConfig::None,
+        },
+        ..default()
+    });
+
+    let player1_style = Style {
+        position_type: PositionType::Absolute,
+        top: Val::Px(10.0),
+        left: Val::Px(10.0),
+        width: Val::Px(220.0),
+        height: Val::Px(28.0),
+        ..default()
+    };
+
+    let healthbar_bg_style = Style {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..default()
+    };
+
+    let healthbar_fg_style = Style {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: player1_style.clone(),
+            background_color: BackgroundColor(Color::NONE),
+            ..default()
+        })
+        .insert(PlayerHealthBar { player_id: 1 })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: healthbar_bg_style.clone(),
+                    background_color: BackgroundColor(Color::rgba(0.1, 0.1, 0.1, 0.6)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: healthbar_fg_style.clone(),
+                            background_color: BackgroundColor(Color::GREEN),
+                            ..default()
+                        })
+                        .insert(UiHealthBar);
+                });
+        });
+
+    let player2_style = Style {
+        position_type: PositionType::Absolute,
+        top: Val::Px(10.0),
+        right: Val::Px(10.0),
+        width: Val::Px(220.0),
+        height: Val::Px(28.0),
+        ..default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: player2_style,
+            background_color: BackgroundColor(Color::NONE),
+            ..default()
+        })
+        .insert(PlayerHealthBar { player_id: 2 })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: healthbar_bg_style,
+                    background_color: BackgroundColor(Color::rgba(0.1, 0.1, 0.1, 0.6)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: healthbar_fg_style,
+                            background_color: BackgroundColor(Color::GREEN),
+                            ..default()
+                        })
+                        .insert(UiHealthBar);
+                });
+        });
+
+    for player_id in [1, 2] {
+        let (left, right) = match player_id {
+            1 => (Val::Px(10.0), Val::Auto),
+            _ => (Val::Auto, Val::Px(10.0)),
+        };
+        commands.spawn((
+            TextBundle::from_sections([
+                TextSection::new(
+                    "",
+                    TextStyle {
+                        font_size: 32.0,
+                        color: Color::rgba(1.0, 0.85, 0.2, 0.0),
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    "",
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.0),
+                        ..default()
+                    },
+                ),
+            ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(44.0),
+                left,
+                right,
+                ..default()
+            }),
+            ComboCounter { player_id },
+        ));
+    }
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(35.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 56.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center),
+                RoundBanner,
+            ));
+        });
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(6.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 36.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                RoundClockText,
+            ));
+        });
+}
+
+fn update_healthbar_ui_system(
+    players: Query<(&Player, &Health)>,
+    bars: Query<(&PlayerHealthBar, &Children)>,
+    mut fills: Query<&mut Style, With<UiHealthBar>>,
+) {
+    use std::collections::HashMap;
+    let mut health_map = HashMap::new();
+    for (player, health) in players.iter() {
+        health_map.insert(player.id, (health.current / health.max).clamp(0.0, 1.0));
+    }
+    for (bar, children) in bars.iter() {
+        if let Some(ratio) = health_map.get(&bar.player_id) {
+            for &child in children.iter() {
+                if let Ok(mut style) = fills.get_mut(child) {
+                    style.width = Val::Percent(ratio * 100.0);
+                }
+            }
+        }
+    }
+}
+
+fn combo_counter_system(
+    time: Res<Time>,
+    fighters: Query<(&Player, &Combo)>,
+    mut counters: Query<(&ComboCounter, &mut Text)>,
+) {
+    for (counter, mut text) in counters.iter_mut() {
+        let Some((_, combo)) = fighters
+            .iter()
+            .find(|(player, _)| player.id == counter.player_id)
+        else {
+            continue;
+        };
+        if combo.defender.is_some() && combo.hits >= 2 {
+            text.sections[0].value = format!("{} HITS\n", combo.hits);
+            text.sections[1].value = format!("{:.0} DAMAGE", combo.damage);
+            for section in text.sections.iter_mut() {
+                section.style.color.set_a(1.0);
+            }
+        } else {
+            for section in text.sections.iter_mut() {
+                let alpha = section.style.color.a() - time.delta_seconds() / COMBO_FADE_SECS;
+                section.style.color.set_a(alpha.max(0.0));
+            }
+        }
+    }
+}
+
+fn round_clock_text_system(
+    clock: Res<RoundClock>,
+    mut texts: Query<&mut Text, With<RoundClockText>>,
+) {
+    if !clock.is_changed() {
+        return;
+    }
+    let value = match clock.0 {
+        Some(frames) => format!("{}", (frames as f32 / TICK_RATE).ceil() as u32),
+        None => String::new(),
+    };
+    for mut text in texts.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = value.clone();
+        }
+    }
+}
+
+fn round_banner_system(
+    state: Res<State<RoundState>>,
+    score: Res<MatchScore>,
+    config: Res<MatchConfig>,
+    mut banner: Query<&mut Text, With<RoundBanner>>,
+) {
+    let value = match state.get() {
+        RoundState::RoundIntro => format!("ROUND {}", score.round),
+        RoundState::Fighting => String::new(),
+        RoundState::Ko if score.time_over => "TIME".to_string(),
+        RoundState::Ko => "K.O.".to_string(),
+        RoundState::RoundOver => {
+            let headline = match score.round_winner {
+                Some(id) => format!("PLAYER {id} WINS THE ROUND"),
+                None if score.time_over => "DRAW".to_string(),
+                None => "DOUBLE K.O.".to_string(),
+            };
+            format!("{headline}\n{} - {}", score.wins[0], score.wins[1])
+        }
+        RoundState::MatchOver => {
+            let rounds_to_win = config.rounds_to_win();
+            let headline = match (
+                score.wins[0] >= rounds_to_win,
+                score.wins[1] >= rounds_to_win,
+            ) {
+                (true, false) => "PLAYER 1 WINS THE MATCH",
+                (false, true) => "PLAYER 2 WINS THE MATCH",
+                _ => "DRAW GAME",
+            };
+            format!("{headline}\nPress Enter for a rematch")
+        }
+    };
+    for mut text in banner.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = value.clone();
+        }
+    }
+}
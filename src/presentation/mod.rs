@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+pub mod assets;
+pub mod camera;
+pub mod controls;
+pub mod hud;
+pub mod settings;
+pub mod skeleton;
+pub mod visuals;
+
+pub use assets::{AssetLoadState, FighterAssets};
+pub use camera::MainCamera;
+pub use hud::{PlayerHealthBar, RoundBanner, UiHealthBar};
+pub use settings::{Settings, SettingsPath};
+pub use skeleton::{FighterAnimation, FighterBones};
+pub use visuals::{HealthBar, HitboxVisual};
+
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ClearColor(Color::rgb(0.05, 0.05, 0.08)))
+            .add_plugins((
+                assets::AssetLoadingPlugin,
+                camera::CameraRigPlugin,
+                controls::KeyboardControlsPlugin,
+                hud::HudPlugin,
+                settings::SettingsPlugin,
+                skeleton::SkeletonPlugin,
+                visuals::VisualsPlugin,
+            ));
+    }
+}
@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowMode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::simulation::{Action, GameSet, MatchConfig, ARENA_WIDTH};
+
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub controls: KeyBindings,
+    pub audio: AudioSettings,
+    pub video: VideoSettings,
+    pub debug: DebugSettings,
+    pub match_rules: MatchConfig,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub player1: PlayerBindings,
+    pub player2: PlayerBindings,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PlayerBindings {
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub punch: KeyCode,
+    pub kick: KeyCode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jump: Option<KeyCode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub mode: WindowMode,
+    pub resolution: (f32, f32),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebugSettings {
+    pub show_hitboxes: bool,
+}
+
+/// Where [`Settings`] are saved; `None` when there is no config directory.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SettingsPath(pub Option<PathBuf>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            player1: PlayerBindings {
+                left: KeyCode::A,
+                right: KeyCode::D,
+                up: KeyCode::W,
+                down: KeyCode::S,
+                punch: KeyCode::J,
+                kick: KeyCode::K,
+                jump: Some(KeyCode::Space),
+            },
+            player2: PlayerBindings {
+                left: KeyCode::Left,
+                right: KeyCode::Right,
+                up: KeyCode::Up,
+                down: KeyCode::Down,
+                punch: KeyCode::Numpad1,
+                kick: KeyCode::Numpad2,
+                jump: Some(KeyCode::Numpad3),
+            },
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn player(&self, player_id: usize) -> Option<&PlayerBindings> {
+        match player_id {
+            1 => Some(&self.player1),
+            2 => Some(&self.player2),
+            _ => None,
+        }
+    }
+
+    /// Binds actions a settings file leaves out to the keys in `defaults`.
+    fn fill_missing(&mut self, defaults: &KeyBindings) {
+        self.player1.fill_missing(&defaults.player1);
+        self.player2.fill_missing(&defaults.player2);
+    }
+}
+
+impl PlayerBindings {
+    pub fn iter(&self) -> impl Iterator<Item = (Action, KeyCode)> {
+        [
+            (Action::Left, Some(self.left)),
+            (Action::Right, Some(self.right)),
+            (Action::Up, Some(self.up)),
+            (Action::Down, Some(self.down)),
+            (Action::Punch, Some(self.punch)),
+            (Action::Kick, Some(self.kick)),
+            (Action::Jump, self.jump),
+        ]
+        .into_iter()
+        .filter_map(|(action, key)| Some((action, key?)))
+    }
+
+    fn fill_missing(&mut self, defaults: &PlayerBindings) {
+        self.jump = self.jump.or(defaults.jump);
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self { master_volume: 1. }
+    }
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            mode: WindowMode::Windowed,
+            resolution: (ARENA_WIDTH, 600.),
+        }
+    }
+}
+
+impl Default for DebugSettings {
+    fn default() -> Self {
+        Self {
+            show_hitboxes: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("tekken").join("settings.toml"))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        let mut settings: Self = toml::from_str(&text)
+            .map_err(|err| format!("invalid settings {}: {err}", path.display()))?;
+        // Files saved before an action existed have no key for it.
+        settings.controls.fill_missing(&KeyBindings::default());
+        Ok(settings)
+    }
+
+    /// Loads the settings file, falling back to defaults when it is missing or corrupt.
+    pub fn load_or_default(path: Option<&Path>) -> Self {
+        let Some(path) = path.filter(|path| path.exists()) else {
+            return Self::default();
+        };
+        Self::load(path).unwrap_or_else(|err| {
+            eprintln!("tekken: {err}, resetting to defaults");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text =
+            toml::to_string_pretty(self).map_err(|err| format!("cannot encode settings: {err}"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("cannot create {}: {err}", dir.display()))?;
+        }
+        std::fs::write(path, text).map_err(|err| format!("cannot write {}: {err}", path.display()))
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .init_resource::<SettingsPath>()
+            .add_systems(Update, settings_hotkey_system.in_set(GameSet::Input))
+            .add_systems(
+                Last,
+                save_settings_system.run_if(resource_changed::<Settings>()),
+            );
+    }
+}
+
+fn settings_hotkey_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if keyboard.just_pressed(KeyCode::F1) {
+        settings.debug.show_hitboxes = !settings.debug.show_hitboxes;
+    }
+    if keyboard.just_pressed(KeyCode::F11) {
+        settings.video.mode = match settings.video.mode {
+            WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+            _ => WindowMode::Windowed,
+        };
+        for mut window in windows.iter_mut() {
+            window.mode = settings.video.mode;
+        }
+    }
+}
+
+fn save_settings_system(settings: Res<Settings>, path: Res<SettingsPath>) {
+    let Some(path) = &path.0 else {
+        return;
+    };
+    if let Err(err) = settings.save(path) {
+        error!("{err}");
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::animation::{AnimationPlayer, EntityPath, Keyframes};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::settings::Settings;
+use crate::simulation::{GameSet, HurtboxRig, Hurtboxes, Joint, Player, SimFrame, Track};
+
+/// Named nodes of a fighter's loaded model.
+#[derive(Component, Default, Deref)]
+pub struct FighterBones(HashMap<String, Entity>);
+
+/// The clip a fighter's model plays and the node playing it.
+#[derive(Component, Clone, Debug)]
+pub struct FighterAnimation {
+    pub player: Entity,
+    pub clip: Handle<AnimationClip>,
+}
+
+pub struct SkeletonPlugin;
+
+impl Plugin for SkeletonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                collect_fighter_bones_system,
+                build_hurtbox_rig_system,
+                sync_animation_system,
+                draw_hurtboxes_system,
+            )
+                .chain()
+                .in_set(GameSet::Presentation),
+        );
+    }
+}
+
+fn collect_fighter_bones_system(
+    mut commands: Commands,
+    fighters: Query<Entity, (With<Player>, Without<FighterBones>)>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+) {
+    for entity in fighters.iter() {
+        let bones: HashMap<_, _> = children
+            .iter_descendants(entity)
+            .filter_map(|node| Some((names.get(node).ok()?.as_str().to_string(), node)))
+            .collect();
+        if !bones.is_empty() {
+            commands.entity(entity).insert(FighterBones(bones));
+        }
+    }
+}
+
+/// Hands the simulation the skeleton and clip its hurtboxes follow. The rig is rebuilt when a
+/// reloaded definition names a bone it does not have yet.
+fn build_hurtbox_rig_system(
+    mut commands: Commands,
+    fighters: Query<(
+        Entity,
+        &FighterBones,
+        &FighterAnimation,
+        &Hurtboxes,
+        Option<&HurtboxRig>,
+    )>,
+    parents: Query<&Parent>,
+    nodes: Query<(&Transform, Option<&Name>)>,
+    clips: Res<Assets<AnimationClip>>,
+) {
+    for (entity, fighter_bones, animation, hurtboxes, rig) in fighters.iter() {
+        let Some(clip) = clips.get(&animation.clip) else {
+            continue;
+        };
+        let wanted: Vec<_> = hurtboxes
+            .iter()
+            .filter_map(|hurtbox| hurtbox.bone.as_ref())
+            .filter(|bone| fighter_bones.contains_key(*bone))
+            .collect();
+        if rig.is_some_and(|rig| wanted.iter().all(|bone| rig.bones.contains_key(*bone))) {
+            continue;
+        }
+        let bones = wanted
+            .into_iter()
+            .filter_map(|bone| {
+                let joints = joint_chain(
+                    entity,
+                    fighter_bones[bone],
+                    animation,
+                    clip,
+                    &parents,
+                    &nodes,
+                )?;
+                Some((bone.clone(), joints))
+            })
+            .collect();
+        commands.entity(entity).insert(HurtboxRig {
+            duration: clip.duration(),
+            bones,
+        });
+    }
+}
+
+/// Joints from just below `fighter` down to `bone`, with the clip's keyframes for each.
+fn joint_chain(
+    fighter: Entity,
+    bone: Entity,
+    animation: &FighterAnimation,
+    clip: &AnimationClip,
+    parents: &Query<&Parent>,
+    nodes: &Query<(&Transform, Option<&Name>)>,
+) -> Option<Vec<Joint>> {
+    let mut chain = vec![bone];
+    loop {
+        let parent = parents.get(*chain.last()?).ok()?.get();
+        if parent == fighter {
+            break;
+        }
+        chain.push(parent);
+    }
+    chain.reverse();
+
+    // Clip paths are the names of the nodes from the animation player down.
+    let player = chain.iter().position(|&node| node == animation.player);
+    (0..chain.len())
+        .map(|i| {
+            let (rest, _) = nodes.get(chain[i]).ok()?;
+            let mut joint = Joint {
+                rest: *rest,
+                ..default()
+            };
+            let path = player.filter(|&player| player <= i).and_then(|player| {
+                let parts = chain[player..=i]
+                    .iter()
+                    .map(|&node| nodes.get(node).ok()?.1.cloned())
+                    .collect::<Option<_>>()?;
+                Some(EntityPath { parts })
+            });
+            let curves = path.and_then(|path| clip.get_curves_by_path(&path));
+            for curve in curves.into_iter().flatten() {
+                let times = curve.keyframe_timestamps.clone();
+                if times.is_empty() {
+                    continue;
+                }
+                match &curve.keyframes {
+                    Keyframes::Translation(values) => {
+                        joint.translation = Some(Track {
+                            times,
+                            values: values.clone(),
+                        });
+                    }
+                    Keyframes::Rotation(values) => {
+                        joint.rotation = Some(Track {
+                            times,
+                            values: values.clone(),
+                        });
+                    }
+                    Keyframes::Scale(values) => {
+                        joint.scale = Some(Track {
+                            times,
+                            values: values.clone(),
+                        });
+                    }
+                    Keyframes::Weights(_) => {}
+                }
+            }
+            Some(joint)
+        })
+        .collect()
+}
+
+/// Shows the model in the pose the simulation last hit-tested against.
+fn sync_animation_system(
+    frame: Res<SimFrame>,
+    fighters: Query<(&FighterAnimation, &HurtboxRig)>,
+    mut players: Query<&mut AnimationPlayer>,
+) {
+    for (animation, rig) in fighters.iter() {
+        if let Ok(mut player) = players.get_mut(animation.player) {
+            player.set_elapsed(rig.clip_time(**frame));
+        }
+    }
+}
+
+fn draw_hurtboxes_system(
+    mut gizmos: Gizmos,
+    settings: Res<Settings>,
+    fighters: Query<(&Transform, &Hurtboxes)>,
+) {
+    if !settings.debug.show_hitboxes {
+        return;
+    }
+    for (transform, hurtboxes) in fighters.iter() {
+        for hurtbox in hurtboxes.iter() {
+            let centre = hurtbox.world_transform(transform).translation;
+            let radius = hurtbox.shape.min_half_extent();
+            gizmos.sphere(centre, transform.rotation, radius, Color::YELLOW);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::animation::AnimationPlayer;
+use bevy::prelude::*;
+
+use super::assets::{AssetLoadState, FighterAssets};
+use super::settings::Settings;
+use super::skeleton::FighterAnimation;
+use crate::simulation::{
+    Fighter, GameEntity, GameSet, Health, Hitbox, Player, RoundState, Stage, StageCollider,
+    ARENA_WIDTH,
+};
+
+#[derive(Component)]
+pub struct HitboxVisual {
+    pub owner: Entity,
+}
+
+#[derive(Component)]
+pub struct HealthBar;
+
+#[derive(Component, Default)]
+pub struct AnimationStarted(bool);
+
+pub struct VisualsPlugin;
+
+impl Plugin for VisualsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup)
+            .add_systems(OnEnter(RoundState::RoundIntro), clear_hitbox_visuals_system)
+            .add_systems(
+                Update,
+                (
+                    attach_fighter_visuals_system.run_if(in_state(AssetLoadState::Ready)),
+                    attach_stage_visuals_system,
+                    update_healthbar_system,
+                    play_animation_system,
+                    draw_hitboxes_system,
+                )
+                    .in_set(GameSet::Presentation),
+            );
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    stage: Res<Stage>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                shadows_enabled: false,
+                illuminance: 20000.0,
+                ..default()
+            },
+            transform: Transform::from_rotation(Quat::from_euler(
+                EulerRot::XYZ,
+                -std::f32::consts::FRAC_PI_4,
+                std::f32::consts::FRAC_PI_6,
+                0.0,
+            )),
+            ..default()
+        },
+        GameEntity,
+    ));
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane {
+                size: ARENA_WIDTH,
+                subdivisions: 1,
+            })),
+            material: materials.add(StandardMaterial {
+                base_color: floor_color(*stage),
+                perceptual_roughness: 1.0,
+                ..default()
+            }),
+            transform: Transform::from_xyz(ARENA_WIDTH / 2., 0.0, 0.0),
+            ..default()
+        },
+        GameEntity,
+    ));
+}
+
+fn attach_stage_visuals_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    colliders: Query<(Entity, &Transform, &StageCollider), Added<StageCollider>>,
+) {
+    for (entity, transform, collider) in colliders.iter() {
+        let size = collider.half_extents * 2.0;
+        commands.entity(entity).insert(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgb(0.8, 0.2, 0.2),
+                ..default()
+            }),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
+
+fn floor_color(stage: Stage) -> Color {
+    match stage {
+        Stage::Dojo => Color::rgb(0.08, 0.6, 0.2),
+        Stage::Sunset => Color::rgb(0.75, 0.4, 0.15),
+        Stage::Void => Color::rgb(0.15, 0.12, 0.25),
+    }
+}
+
+fn attach_fighter_visuals_system(
+    mut commands: Commands,
+    fighter_assets: Res<FighterAssets>,
+    fighters: Query<(Entity, &Fighter), Added<Player>>,
+) {
+    for (entity, fighter) in fighters.iter() {
+        let scene = fighter
+            .model
+            .as_ref()
+            .and_then(|model| fighter_assets.scenes.get(model));
+        commands
+            .entity(entity)
+            .insert(VisibilityBundle::default())
+            .insert(AnimationStarted::default())
+            .with_children(|parent| {
+                match scene {
+                    Some(scene) => {
+                        parent.spawn(SceneBundle {
+                            scene: scene.clone(),
+                            transform: Transform::from_scale(Vec3::splat(80.0)),
+                            ..default()
+                        });
+                    }
+                    None => {
+                        parent.spawn(PbrBundle {
+                            mesh: fighter_assets.body_mesh.clone(),
+                            material: fighter_assets.body_material.clone(),
+                            transform: Transform::from_xyz(0., 40., 0.),
+                            ..default()
+                        });
+                    }
+                }
+                parent.spawn((
+                    PbrBundle {
+                        mesh: fighter_assets.healthbar_mesh.clone(),
+                        material: fighter_assets.healthbar_material.clone(),
+                        transform: Transform::from_xyz(0.0, 160.0, 0.0),
+                        ..default()
+                    },
+                    HealthBar,
+                    GameEntity,
+                ));
+            });
+    }
+}
+
+fn clear_hitbox_visuals_system(mut commands: Commands, visuals: Query<Entity, With<HitboxVisual>>) {
+    for entity in visuals.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn update_healthbar_system(
+    query: Query<(Entity, &Health, &Children)>,
+    mut healthbars: Query<&mut Transform, With<HealthBar>>,
+) {
+    for (_entity, health, children) in query.iter() {
+        for child in children.iter() {
+            if let Ok(mut healthbar_transform) = healthbars.get_mut(*child) {
+                let ratio = (health.current / health.max).clamp(0.0, 1.0);
+                healthbar_transform.scale.x = ratio;
+            }
+        }
+    }
+}
+
+fn play_animation_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut AnimationStarted), With<Player>>,
+    children: Query<&Children>,
+    mut animation_players: Query<&mut AnimationPlayer>,
+    clips: Res<Assets<AnimationClip>>,
+) {
+    for (entity, mut started) in query.iter_mut() {
+        if started.0 {
+            continue;
+        }
+        for child in children.iter_descendants(entity) {
+            if let Ok(mut anim_player) = animation_players.get_mut(child) {
+                if let Some((handle_id, _)) = clips.iter().next() {
+                    let handle = Handle::<AnimationClip>::weak(handle_id);
+                    // The simulation clock moves the clip on; see `sync_animation_system`.
+                    anim_player.play(handle.clone()).repeat().pause();
+                    commands.entity(entity).insert(FighterAnimation {
+                        player: child,
+                        clip: handle,
+                    });
+                    started.0 = true;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn draw_hitboxes_system(
+    mut commands: Commands,
+    fighter_assets: Res<FighterAssets>,
+    settings: Res<Settings>,
+    hitboxes: Query<(Entity, &Hitbox, &Transform), Without<HitboxVisual>>,
+    mut visuals: Query<(Entity, &HitboxVisual, &mut Transform)>,
+) {
+    use std::collections::HashSet;
+    let show = settings.debug.show_hitboxes;
+    let visual_transform = |hitbox: &Hitbox, transform: &Transform| {
+        transform.with_scale(hitbox.shape.half_extents() * 2.0)
+    };
+    let mut owners_with_visual = HashSet::new();
+    for (vis_entity, vis, mut vis_transform) in visuals.iter_mut() {
+        match hitboxes.get(vis.owner) {
+            Ok((_, hitbox, transform)) if show => {
+                *vis_transform = visual_transform(hitbox, transform);
+                owners_with_visual.insert(vis.owner);
+            }
+            _ => commands.entity(vis_entity).despawn(),
+        }
+    }
+    if !show {
+        return;
+    }
+    for (hb_entity, hitbox, transform) in hitboxes.iter() {
+        if owners_with_visual.contains(&hb_entity) {
+            continue;
+        }
+        commands.spawn((
+            PbrBundle {
+                mesh: fighter_assets.hitbox_mesh.clone(),
+                material: fighter_assets.hitbox_material.clone(),
+                transform: visual_transform(hitbox, transform),
+                ..default()
+            },
+            HitboxVisual { owner: hb_entity },
+            GameEntity,
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use super::collision::Shape;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn from_shape(shape: &Shape, transform: &Transform) -> Self {
+        let rotation = Mat3::from_quat(transform.rotation);
+        let local = shape.half_extents();
+        let half = Vec3::new(
+            rotation.row(0).abs().dot(local),
+            rotation.row(1).abs().dot(local),
+            rotation.row(2).abs().dot(local),
+        );
+        Self {
+            min: transform.translation - half,
+            max: transform.translation + half,
+        }
+    }
+
+    /// Box covering a shape over its whole motion between two transforms.
+    pub fn swept(shape: &Shape, from: &Transform, to: &Transform) -> Self {
+        Self::from_shape(shape, from).union(&Self::from_shape(shape, to))
+    }
+
+    pub fn union(&self, other: &Aabb) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// Finds every pair of boxes from `a` and `b` that intersect, by sweeping both sets along X.
+/// Pairs are returned as `(index into a, index into b)`, sorted so callers see them in the same
+/// order a nested loop would produce.
+pub fn sweep_and_prune(a: &[Aabb], b: &[Aabb]) -> Vec<(usize, usize)> {
+    let sorted = |boxes: &[Aabb]| {
+        let mut order: Vec<usize> = (0..boxes.len()).collect();
+        order.sort_by(|&i, &j| boxes[i].min.x.total_cmp(&boxes[j].min.x).then(i.cmp(&j)));
+        order
+    };
+    let order_a = sorted(a);
+    let order_b = sorted(b);
+
+    // Whichever box starts first scans the other set for boxes starting before it ends, so
+    // each overlapping pair is found exactly once.
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < order_a.len() && j < order_b.len() {
+        let (ia, jb) = (order_a[i], order_b[j]);
+        if a[ia].min.x <= b[jb].min.x {
+            for &k in order_b[j..]
+                .iter()
+                .take_while(|&&k| b[k].min.x <= a[ia].max.x)
+            {
+                if a[ia].intersects(&b[k]) {
+                    pairs.push((ia, k));
+                }
+            }
+            i += 1;
+        } else {
+            for &k in order_a[i..]
+                .iter()
+                .take_while(|&&k| a[k].min.x <= b[jb].max.x)
+            {
+                if a[k].intersects(&b[jb]) {
+                    pairs.push((k, jb));
+                }
+            }
+            j += 1;
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::rng::SimRng;
+
+    fn scatter(rng: &mut SimRng, count: usize, size: f32) -> Vec<Aabb> {
+        (0..count)
+            .map(|_| {
+                let centre = Vec3::new(rng.next_f32(), rng.next_f32(), rng.next_f32()) * 500.0;
+                let half = Vec3::new(rng.next_f32(), rng.next_f32(), rng.next_f32()) * size;
+                Aabb {
+                    min: centre - half,
+                    max: centre + half,
+                }
+            })
+            .collect()
+    }
+
+    fn brute_force(a: &[Aabb], b: &[Aabb]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for (i, box_a) in a.iter().enumerate() {
+            for (j, box_b) in b.iter().enumerate() {
+                if box_a.intersects(box_b) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn finds_the_same_pairs_as_brute_force() {
+        let mut rng = SimRng::new(7);
+        for (count_a, count_b, size) in
+            [(0, 5, 50.0), (1, 1, 300.0), (20, 30, 60.0), (80, 40, 120.0)]
+        {
+            let a = scatter(&mut rng, count_a, size);
+            let b = scatter(&mut rng, count_b, size);
+            assert_eq!(sweep_and_prune(&a, &b), brute_force(&a, &b));
+        }
+    }
+
+    #[test]
+    fn touching_and_identical_boxes_count_once() {
+        let unit = Aabb {
+            min: Vec3::ZERO,
+            max: Vec3::ONE,
+        };
+        let touching = Aabb {
+            min: Vec3::X,
+            max: Vec3::new(2.0, 1.0, 1.0),
+        };
+        let apart = Aabb {
+            min: Vec3::new(0.0, 2.0, 0.0),
+            max: Vec3::new(1.0, 3.0, 1.0),
+        };
+        let a = [unit, unit];
+        let b = [apart, touching, unit];
+        assert_eq!(
+            sweep_and_prune(&a, &b),
+            vec![(0, 1), (0, 2), (1, 1), (1, 2)]
+        );
+    }
+}
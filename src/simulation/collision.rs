@@ -0,0 +1,359 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::GameSet;
+
+/// Collision volume in the local space of whatever carries it. Capsules stand along local Y.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Box { half_extents: Vec3 },
+    Capsule { radius: f32, half_height: f32 },
+}
+
+impl Shape {
+    /// Smallest distance from the centre to the surface along a local axis.
+    pub fn min_half_extent(&self) -> f32 {
+        match *self {
+            Shape::Box { half_extents } => half_extents.min_element(),
+            Shape::Capsule { radius, .. } => radius,
+        }
+    }
+
+    /// Half extents of the shape's local bounding box.
+    pub fn half_extents(&self) -> Vec3 {
+        match *self {
+            Shape::Box { half_extents } => half_extents,
+            Shape::Capsule {
+                radius,
+                half_height,
+            } => Vec3::new(radius, half_height + radius, radius),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BodyRegion {
+    Head,
+    #[default]
+    Torso,
+    Legs,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Hurtbox {
+    pub shape: Shape,
+    /// Centre of the shape relative to a fighter facing +X.
+    pub offset: Vec3,
+    #[serde(default)]
+    pub region: BodyRegion,
+    /// Skeleton bone the hurtbox follows, replacing `offset`, once the fighter has a
+    /// [`HurtboxRig`](super::rig::HurtboxRig).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bone: Option<String>,
+}
+
+impl Hurtbox {
+    pub fn world_transform(&self, owner: &Transform) -> Transform {
+        Transform::from_translation(owner.translation + owner.rotation * self.offset)
+            .with_rotation(owner.rotation)
+    }
+}
+
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct Hurtboxes(pub Vec<Hurtbox>);
+
+/// Where an entity was at the start of the current tick, used for swept tests.
+#[derive(Component, Clone, Copy, Debug, Default, Deref, DerefMut)]
+pub struct PreviousTransform(pub Transform);
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            store_previous_transform_system.before(GameSet::Input),
+        );
+    }
+}
+
+fn store_previous_transform_system(mut query: Query<(&Transform, &mut PreviousTransform)>) {
+    for (transform, mut previous) in query.iter_mut() {
+        previous.0 = *transform;
+    }
+}
+
+/// Tests whether two shapes touch at any point while both move from their `from` to their `to`
+/// transforms. The relative motion is sampled finely enough that neither shape can skip over
+/// the other, so the result does not depend on how far they travelled in one tick; the cost
+/// grows with that distance instead.
+pub fn swept_overlaps(
+    a: &Shape,
+    a_from: &Transform,
+    a_to: &Transform,
+    b: &Shape,
+    b_from: &Transform,
+    b_to: &Transform,
+) -> bool {
+    let travel = (a_to.translation - a_from.translation) - (b_to.translation - b_from.translation);
+    let step = a.min_half_extent().min(b.min_half_extent()).max(1.0);
+    let steps = (travel.length() / step).ceil().max(1.0) as u32;
+    (0..=steps).rev().any(|i| {
+        let t = i as f32 / steps as f32;
+        overlaps(a, &lerp(a_from, a_to, t), b, &lerp(b_from, b_to, t))
+    })
+}
+
+fn lerp(from: &Transform, to: &Transform, t: f32) -> Transform {
+    Transform::from_translation(from.translation.lerp(to.translation, t))
+        .with_rotation(from.rotation.slerp(to.rotation, t))
+}
+
+/// Tests whether two shapes overlap. Only translation and rotation of the transforms are used.
+pub fn overlaps(a: &Shape, a_transform: &Transform, b: &Shape, b_transform: &Transform) -> bool {
+    match (*a, *b) {
+        (Shape::Box { half_extents: ha }, Shape::Box { half_extents: hb }) => {
+            boxes_overlap(a_transform, ha, b_transform, hb)
+        }
+        (
+            Shape::Capsule {
+                radius: ra,
+                half_height: la,
+            },
+            Shape::Capsule {
+                radius: rb,
+                half_height: lb,
+            },
+        ) => {
+            let (a0, a1) = capsule_segment(a_transform, la);
+            let (b0, b1) = capsule_segment(b_transform, lb);
+            segment_distance_squared(a0, a1, b0, b1) <= (ra + rb) * (ra + rb)
+        }
+        (
+            Shape::Box { half_extents },
+            Shape::Capsule {
+                radius,
+                half_height,
+            },
+        ) => box_capsule_overlap(a_transform, half_extents, b_transform, radius, half_height),
+        (
+            Shape::Capsule {
+                radius,
+                half_height,
+            },
+            Shape::Box { half_extents },
+        ) => box_capsule_overlap(b_transform, half_extents, a_transform, radius, half_height),
+    }
+}
+
+fn boxes_overlap(a: &Transform, ha: Vec3, b: &Transform, hb: Vec3) -> bool {
+    let axes_a = [
+        a.rotation * Vec3::X,
+        a.rotation * Vec3::Y,
+        a.rotation * Vec3::Z,
+    ];
+    let axes_b = [
+        b.rotation * Vec3::X,
+        b.rotation * Vec3::Y,
+        b.rotation * Vec3::Z,
+    ];
+    let offset = b.translation - a.translation;
+    let separated = |axis: Vec3| {
+        if axis.length_squared() < 1e-6 {
+            return false;
+        }
+        let reach = |axes: &[Vec3; 3], half: Vec3| {
+            half.x * axes[0].dot(axis).abs()
+                + half.y * axes[1].dot(axis).abs()
+                + half.z * axes[2].dot(axis).abs()
+        };
+        offset.dot(axis).abs() > reach(&axes_a, ha) + reach(&axes_b, hb)
+    };
+    if axes_a.iter().chain(&axes_b).any(|&axis| separated(axis)) {
+        return false;
+    }
+    !axes_a
+        .iter()
+        .any(|&ea| axes_b.iter().any(|&eb| separated(ea.cross(eb))))
+}
+
+fn box_capsule_overlap(
+    box_transform: &Transform,
+    half_extents: Vec3,
+    capsule_transform: &Transform,
+    radius: f32,
+    half_height: f32,
+) -> bool {
+    let (start, end) = capsule_segment(capsule_transform, half_height);
+    let to_local = box_transform.rotation.inverse();
+    let start = to_local * (start - box_transform.translation);
+    let end = to_local * (end - box_transform.translation);
+    let distance_squared = |t: f32| {
+        let point = start.lerp(end, t);
+        (point - point.clamp(-half_extents, half_extents)).length_squared()
+    };
+    // The distance from a segment point to a box is convex along the segment.
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..32 {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if distance_squared(m1) <= distance_squared(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+    distance_squared((lo + hi) / 2.0) <= radius * radius
+}
+
+fn capsule_segment(transform: &Transform, half_height: f32) -> (Vec3, Vec3) {
+    let axis = transform.rotation * Vec3::Y * half_height;
+    (transform.translation - axis, transform.translation + axis)
+}
+
+fn segment_distance_squared(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> f32 {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.dot(d1);
+    let e = d2.dot(d2);
+    let f = d2.dot(r);
+    let (s, t) = if a <= f32::EPSILON && e <= f32::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f32::EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= f32::EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > f32::EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    (p1 + d1 * s).distance_squared(p2 + d2 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    const CUBE: Shape = Shape::Box {
+        half_extents: Vec3::splat(10.0),
+    };
+    const CAPSULE: Shape = Shape::Capsule {
+        radius: 5.0,
+        half_height: 10.0,
+    };
+
+    fn at(x: f32, y: f32, z: f32) -> Transform {
+        Transform::from_xyz(x, y, z)
+    }
+
+    /// Whether `b` placed at `b_at` overlaps `a` placed at the origin.
+    fn touches(a: Shape, b: Shape, b_at: Transform) -> bool {
+        overlaps(&a, &Transform::IDENTITY, &b, &b_at)
+    }
+
+    #[test]
+    fn boxes_overlap_until_their_faces_part() {
+        assert!(touches(CUBE, CUBE, at(19.0, 0.0, 0.0)));
+        assert!(!touches(CUBE, CUBE, at(21.0, 0.0, 0.0)));
+        assert!(!touches(CUBE, CUBE, at(0.0, 0.0, -21.0)));
+    }
+
+    #[test]
+    fn rotated_box_reaches_further_along_its_diagonal() {
+        let turned = Quat::from_rotation_y(FRAC_PI_4);
+        assert!(touches(
+            CUBE,
+            CUBE,
+            at(24.0, 0.0, 0.0).with_rotation(turned)
+        ));
+        assert!(!touches(CUBE, CUBE, at(24.0, 0.0, 0.0)));
+        // A box balanced on an edge dips into the one below it.
+        let tilted = Quat::from_rotation_x(FRAC_PI_4);
+        assert!(touches(
+            CUBE,
+            CUBE,
+            at(0.0, 24.0, 0.0).with_rotation(tilted)
+        ));
+        assert!(!touches(
+            CUBE,
+            CUBE,
+            at(0.0, 25.0, 0.0).with_rotation(tilted)
+        ));
+    }
+
+    #[test]
+    fn capsules_overlap_within_their_radii() {
+        assert!(touches(CAPSULE, CAPSULE, at(9.0, 0.0, 0.0)));
+        assert!(!touches(CAPSULE, CAPSULE, at(11.0, 0.0, 0.0)));
+        // The rounded ends only reach one radius past the segment.
+        assert!(touches(CAPSULE, CAPSULE, at(0.0, 29.0, 0.0)));
+        assert!(!touches(CAPSULE, CAPSULE, at(0.0, 31.0, 0.0)));
+    }
+
+    #[test]
+    fn box_and_capsule_overlap_in_either_order() {
+        let lying = Quat::from_rotation_z(FRAC_PI_2);
+        for (capsule, hit) in [
+            (at(14.0, 0.0, 0.0), true),
+            (at(16.0, 0.0, 0.0), false),
+            (at(24.0, 0.0, 0.0).with_rotation(lying), true),
+            (at(26.0, 0.0, 0.0).with_rotation(lying), false),
+        ] {
+            assert_eq!(touches(CUBE, CAPSULE, capsule), hit, "{capsule:?}");
+            // Seen from the capsule, the cube sits at the inverse transform.
+            let cube = Transform::from_matrix(capsule.compute_matrix().inverse());
+            assert_eq!(touches(CAPSULE, CUBE, cube), hit, "{capsule:?}");
+        }
+    }
+
+    #[test]
+    fn segment_distance_handles_degenerate_and_parallel_segments() {
+        let d = |p1, q1, p2, q2| segment_distance_squared(p1, q1, p2, q2).sqrt();
+        let (o, x, y) = (Vec3::ZERO, Vec3::X, Vec3::Y);
+        assert!((d(o, o, x * 3.0, x * 3.0) - 3.0).abs() < 1e-5);
+        assert!((d(o, o, y - x, y + x) - 1.0).abs() < 1e-5);
+        assert!((d(o, x * 2.0, y, y + x * 2.0) - 1.0).abs() < 1e-5);
+        assert!((d(o, x, x * 3.0, x * 4.0) - 2.0).abs() < 1e-5);
+        assert!(d(-x, x, -y, y) < 1e-5);
+    }
+
+    #[test]
+    fn fast_shapes_do_not_tunnel_through_each_other() {
+        let small = Shape::Box {
+            half_extents: Vec3::splat(2.0),
+        };
+        let still = at(0.0, 0.0, 0.0);
+        let from = at(-100.0, 0.0, 0.0);
+        let to = at(100.0, 0.0, 0.0);
+        assert!(!overlaps(&small, &to, &CUBE, &still));
+        assert!(swept_overlaps(&small, &from, &to, &CUBE, &still, &still));
+        // Far enough that 64 samples, the old cap, would step right over the cube.
+        let far = (at(-2030.0, 0.0, 0.0), at(1970.0, 0.0, 0.0));
+        assert!(swept_overlaps(
+            &small, &far.0, &far.1, &CUBE, &still, &still
+        ));
+        let above = (at(-100.0, 30.0, 0.0), at(100.0, 30.0, 0.0));
+        assert!(!swept_overlaps(
+            &small, &above.0, &above.1, &CUBE, &still, &still
+        ));
+    }
+}
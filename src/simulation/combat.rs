@@ -0,0 +1,499 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::broad_phase::{sweep_and_prune, Aabb};
+use super::collision::{swept_overlaps, Hurtboxes, PreviousTransform, Shape};
+use super::config::MatchConfig;
+use super::definition::Fighter;
+use super::events::{BlockEvent, HitEvent, KoEvent, WhiffEvent};
+use super::fighter::{GameEntity, Health, Velocity};
+use super::input::{Action, ControlBasis, FighterInput};
+use super::physics::clamp_to_arena;
+use super::state::FighterState;
+use super::{GameSet, SimFrame};
+
+/// Each earlier hit in a juggle scales the lift and hitstun of the next one by this much.
+const JUGGLE_DECAY: f32 = 0.8;
+/// Extra gravity per hit in a juggle, as a fraction of normal gravity.
+const JUGGLE_GRAVITY_STEP: f32 = 0.15;
+/// Minimum upward speed an air hit gives a juggled fighter before scaling.
+const JUGGLE_LIFT: f32 = 250.;
+/// Damage lost by each hit of a combo for every hit landed before it.
+const COMBO_DAMAGE_DECAY: f32 = 0.1;
+const COMBO_MIN_DAMAGE_SCALE: f32 = 0.3;
+
+#[derive(Component)]
+pub struct Hitbox {
+    pub owner: Entity,
+    pub attack: AttackKind,
+    pub damage: f32,
+    pub hitstun: u32,
+    /// Velocity given to the defender on hit, relative to the owner facing +X.
+    pub knockback: Vec3,
+    pub knockdown: bool,
+    pub launcher: bool,
+    pub low: bool,
+    pub chip_damage: f32,
+    pub blockstun: u32,
+    pub pushback: f32,
+    pub shape: Shape,
+    /// Centre of the hitbox relative to the owner, which it follows while active.
+    pub offset: Vec3,
+    pub max_hits: u32,
+    pub hit_interval: u32,
+    /// Targets this move instance has already struck.
+    pub struck: Vec<Struck>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Struck {
+    pub target: Entity,
+    pub hits: u32,
+    pub last_frame: u64,
+}
+
+impl Hitbox {
+    pub fn can_hit(&self, target: Entity, frame: u64) -> bool {
+        match self.struck.iter().find(|struck| struck.target == target) {
+            Some(struck) => {
+                struck.hits < self.max_hits && frame - struck.last_frame >= self.hit_interval as u64
+            }
+            None => self.max_hits > 0,
+        }
+    }
+
+    pub fn record_hit(&mut self, target: Entity, frame: u64) {
+        match self
+            .struck
+            .iter_mut()
+            .find(|struck| struck.target == target)
+        {
+            Some(struck) => {
+                struck.hits += 1;
+                struck.last_frame = frame;
+            }
+            None => self.struck.push(Struck {
+                target,
+                hits: 1,
+                last_frame: frame,
+            }),
+        }
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Lifetime(pub u32);
+
+/// Hits taken since the fighter was last on the ground.
+#[derive(Component, Default)]
+pub struct Juggle {
+    pub hits: u32,
+}
+
+impl Juggle {
+    /// Multiplier on the lift and hitstun of the next air hit.
+    pub fn scale(&self) -> f32 {
+        JUGGLE_DECAY.powi(self.hits as i32)
+    }
+
+    pub fn gravity_scale(&self) -> f32 {
+        1.0 + JUGGLE_GRAVITY_STEP * self.hits as f32
+    }
+}
+
+/// Consecutive hits an attacker has landed on a defender kept in hitstun.
+#[derive(Component, Default)]
+pub struct Combo {
+    pub hits: u32,
+    pub damage: f32,
+    /// Fighter on the receiving end; `None` once the combo has dropped.
+    pub defender: Option<Entity>,
+}
+
+impl Combo {
+    /// Multiplier on the damage of the next hit.
+    pub fn damage_scale(&self) -> f32 {
+        (1.0 - COMBO_DAMAGE_DECAY * self.hits as f32).max(COMBO_MIN_DAMAGE_SCALE)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct AttackAnimationState {
+    pub kind: Option<AttackKind>,
+    pub end_pos: Vec3,
+    pub connected: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttackKind {
+    Punch,
+    Kick,
+    JumpKick,
+    /// Rising punch that launches the defender into a juggle.
+    Uppercut,
+    /// Low kick performed while getting up from a knockdown.
+    WakeUpKick,
+}
+
+impl AttackKind {
+    /// Whether the move can only be performed in the air.
+    pub fn is_aerial(&self) -> bool {
+        matches!(self, AttackKind::JumpKick)
+    }
+}
+
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                hitbox_lifetime_system,
+                attack_animation_system,
+                apply_deferred,
+                hitbox_follow_system,
+                hitbox_damage_system,
+                combo_drop_system,
+            )
+                .chain()
+                .in_set(GameSet::Combat),
+        );
+    }
+}
+
+fn attack_animation_system(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut FighterState,
+        &mut AttackAnimationState,
+        &Transform,
+        &Fighter,
+    )>,
+    mut whiffs: EventWriter<WhiffEvent>,
+) {
+    for (entity, mut state, mut anim, transform, fighter) in query.iter_mut() {
+        if !state.is_attacking() {
+            anim.kind = None;
+            continue;
+        }
+        let Some((kind, data)) = anim
+            .kind
+            .and_then(|kind| Some((kind, fighter.move_data(kind)?)))
+        else {
+            // A reload dropped the move mid-attack, so there is nothing left to play out.
+            *state = FighterState::Idle;
+            anim.kind = None;
+            continue;
+        };
+        if !state.expired() {
+            continue;
+        }
+        match *state {
+            FighterState::Startup { .. } => {
+                state.transition(FighterState::Active {
+                    frames_left: data.active,
+                });
+                commands.spawn((
+                    Transform::from_translation(anim.end_pos).with_rotation(transform.rotation),
+                    GlobalTransform::default(),
+                    Hitbox {
+                        owner: entity,
+                        attack: kind,
+                        damage: data.damage,
+                        hitstun: data.hitstun,
+                        knockback: data.knockback,
+                        knockdown: data.knockdown,
+                        launcher: data.launcher,
+                        low: data.low,
+                        chip_damage: data.chip_damage,
+                        blockstun: data.blockstun,
+                        pushback: data.pushback,
+                        shape: data.hitbox,
+                        offset: data.offset,
+                        max_hits: data.hits,
+                        hit_interval: data.hit_interval,
+                        struck: Vec::new(),
+                    },
+                    Lifetime(data.active),
+                    GameEntity,
+                ));
+            }
+            FighterState::Active { .. } => {
+                state.transition(FighterState::Recovery {
+                    frames_left: data.recovery,
+                });
+                if !anim.connected {
+                    whiffs.send(WhiffEvent {
+                        attacker: entity,
+                        attack: kind,
+                        position: transform.translation,
+                    });
+                }
+            }
+            FighterState::Recovery { .. } => {
+                state.transition(FighterState::Idle);
+                anim.kind = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn hitbox_lifetime_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Lifetime, Option<&Hitbox>)>,
+    owners: Query<&FighterState>,
+) {
+    for (entity, mut lifetime, hitbox) in query.iter_mut() {
+        **lifetime = lifetime.saturating_sub(1);
+        // A hitbox only lives while its move is active, so getting hit cancels it.
+        let cancelled = hitbox.is_some_and(|hitbox| {
+            !matches!(owners.get(hitbox.owner), Ok(FighterState::Active { .. }))
+        });
+        if **lifetime == 0 || cancelled {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn hitbox_follow_system(
+    mut hitboxes: Query<(&Hitbox, &mut Transform)>,
+    owners: Query<&Transform, Without<Hitbox>>,
+) {
+    for (hitbox, mut transform) in hitboxes.iter_mut() {
+        if let Ok(owner) = owners.get(hitbox.owner) {
+            transform.translation = owner.translation + owner.rotation * hitbox.offset;
+            transform.rotation = owner.rotation;
+        }
+    }
+}
+
+fn hitbox_damage_system(
+    mut hitboxes: Query<(Entity, &mut Hitbox, &Transform)>,
+    mut players: Query<(
+        Entity,
+        &mut Health,
+        &Transform,
+        &PreviousTransform,
+        &Hurtboxes,
+        &Fighter,
+        &mut FighterState,
+        &FighterInput,
+        &mut Velocity,
+        &mut Juggle,
+    )>,
+    mut attackers: Query<(&mut AttackAnimationState, &mut Combo)>,
+    mut hits: EventWriter<HitEvent>,
+    mut blocks: EventWriter<BlockEvent>,
+    mut kos: EventWriter<KoEvent>,
+    basis: Res<ControlBasis>,
+    config: Res<MatchConfig>,
+    frame: Res<SimFrame>,
+) {
+    // Each hitbox is swept along with its owner since the start of the tick.
+    let mut hitbox_entities = Vec::new();
+    let mut hitbox_bounds = Vec::new();
+    for (entity, hitbox, transform) in hitboxes.iter() {
+        let from = players
+            .get_component::<PreviousTransform>(hitbox.owner)
+            .map_or(*transform, |owner| {
+                Transform::from_translation(owner.translation + owner.rotation * hitbox.offset)
+                    .with_rotation(owner.rotation)
+            });
+        hitbox_entities.push((entity, from));
+        hitbox_bounds.push(Aabb::swept(&hitbox.shape, &from, transform));
+    }
+    let mut fighter_entities = Vec::new();
+    let mut fighter_bounds = Vec::new();
+    for (entity, _, transform, previous, hurtboxes, ..) in players.iter() {
+        let bounds = hurtboxes
+            .iter()
+            .map(|hurtbox| {
+                Aabb::swept(
+                    &hurtbox.shape,
+                    &hurtbox.world_transform(previous),
+                    &hurtbox.world_transform(transform),
+                )
+            })
+            .reduce(|a, b| a.union(&b));
+        if let Some(bounds) = bounds {
+            fighter_entities.push(entity);
+            fighter_bounds.push(bounds);
+        }
+    }
+
+    let mut pushes = Vec::new();
+    for (i, j) in sweep_and_prune(&hitbox_bounds, &fighter_bounds) {
+        let (hitbox_entity, hitbox_from) = hitbox_entities[i];
+        let Ok((_, mut hitbox, hitbox_transform)) = hitboxes.get_mut(hitbox_entity) else {
+            continue;
+        };
+        let attacker_position = players
+            .get_component::<Transform>(hitbox.owner)
+            .map_or(hitbox_transform.translation, |owner| owner.translation);
+        let Ok((
+            player_entity,
+            mut health,
+            player_transform,
+            previous,
+            hurtboxes,
+            fighter,
+            mut state,
+            input,
+            mut velocity,
+            mut juggle,
+        )) = players.get_mut(fighter_entities[j])
+        else {
+            continue;
+        };
+        if player_entity == hitbox.owner || !hitbox.can_hit(player_entity, **frame) {
+            continue;
+        }
+        if matches!(*state, FighterState::Knockdown { .. }) && !(hitbox.low && config.otg) {
+            continue;
+        }
+        // When several regions overlap, the one taking the most damage counts.
+        let region = hurtboxes
+            .iter()
+            .filter(|hurtbox| {
+                swept_overlaps(
+                    &hitbox.shape,
+                    &hitbox_from,
+                    hitbox_transform,
+                    &hurtbox.shape,
+                    &hurtbox.world_transform(previous),
+                    &hurtbox.world_transform(player_transform),
+                )
+            })
+            .map(|hurtbox| hurtbox.region)
+            .max_by(|a, b| {
+                let damage = &fighter.region_damage;
+                damage.multiplier(*a).total_cmp(&damage.multiplier(*b))
+            });
+        let Some(region) = region else {
+            continue;
+        };
+        let was_standing = health.current > 0.0;
+        let comboed = state.is_stunned();
+        let away = player_transform.translation - attacker_position;
+        let away = Vec3::new(away.x, 0.0, away.z).normalize_or_zero();
+        let dealt = if state.can_block() && holding_back(input, basis.right, away) {
+            let chip = hitbox.chip_damage * config.damage_multiplier;
+            let floor = if config.chip_ko {
+                0.0
+            } else {
+                health.current.min(1.0)
+            };
+            health.current = (health.current - chip).max(floor);
+            state.transition(FighterState::Blockstun {
+                frames_left: hitbox.blockstun,
+            });
+            // A defender with its back to the arena bound cannot give way, so the attacker does.
+            let position = player_transform.translation;
+            if clamp_to_arena(position + away) == position + away {
+                pushes.push((player_entity, away * hitbox.pushback));
+            } else {
+                pushes.push((hitbox.owner, -away * hitbox.pushback));
+            }
+            blocks.send(BlockEvent {
+                attacker: hitbox.owner,
+                defender: player_entity,
+                attack: hitbox.attack,
+                damage: chip,
+                position: hitbox_transform.translation,
+            });
+            chip
+        } else {
+            // Hits on more vulnerable regions hurt more and stagger for longer.
+            let strength = fighter.region_damage.multiplier(region);
+            let mut damage = hitbox.damage * strength * config.damage_multiplier;
+            if let Ok((_, mut combo)) = attackers.get_mut(hitbox.owner) {
+                if !comboed || combo.defender != Some(player_entity) {
+                    *combo = Combo {
+                        defender: Some(player_entity),
+                        ..default()
+                    };
+                }
+                damage *= combo.damage_scale();
+                combo.hits += 1;
+                combo.damage += damage;
+            }
+            health.current = (health.current - damage).max(0.0);
+            let mut knockback = hitbox_transform.rotation * hitbox.knockback * strength;
+            let hitstun = hitbox.hitstun as f32 * strength;
+            let reaction = if hitbox.knockdown {
+                FighterState::Launched { bounced: false }
+            } else if hitbox.launcher || player_transform.translation.y > 0.0 {
+                // Every hit in the air keeps the defender aloft, a little less each time.
+                let scale = juggle.scale();
+                knockback *= scale;
+                knockback.y = knockback.y.max(JUGGLE_LIFT * scale);
+                juggle.hits += 1;
+                FighterState::Juggled {
+                    frames_left: (hitstun * scale).round() as u32,
+                }
+            } else {
+                FighterState::Hitstun {
+                    frames_left: hitstun.round() as u32,
+                }
+            };
+            if state.transition(reaction) {
+                **velocity = knockback;
+            }
+            hits.send(HitEvent {
+                attacker: hitbox.owner,
+                defender: player_entity,
+                attack: hitbox.attack,
+                damage,
+                region,
+                position: hitbox_transform.translation,
+            });
+            damage
+        };
+        if was_standing && health.current <= 0.0 {
+            kos.send(KoEvent {
+                attacker: hitbox.owner,
+                defender: player_entity,
+                attack: hitbox.attack,
+                damage: dealt,
+                position: hitbox_transform.translation,
+            });
+        }
+        if let Ok((mut anim, _)) = attackers.get_mut(hitbox.owner) {
+            anim.connected = true;
+        }
+        hitbox.record_hit(player_entity, **frame);
+    }
+
+    for (entity, push) in pushes {
+        if let Ok((.., mut velocity, _)) = players.get_mut(entity) {
+            **velocity += push;
+        }
+    }
+}
+
+fn combo_drop_system(mut combos: Query<&mut Combo>, defenders: Query<&FighterState>) {
+    for mut combo in combos.iter_mut() {
+        let Some(defender) = combo.defender else {
+            continue;
+        };
+        if !defenders
+            .get(defender)
+            .is_ok_and(|state| state.is_stunned())
+        {
+            combo.defender = None;
+        }
+    }
+}
+
+/// Whether the fighter is holding the direction pointing away from the attacker.
+fn holding_back(input: &FighterInput, right: Vec3, away: Vec3) -> bool {
+    let back = if away.dot(right) >= 0.0 {
+        Action::Right
+    } else {
+        Action::Left
+    };
+    input.held(back)
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::TICK_RATE;
+
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchConfig {
+    pub starting_health: f32,
+    pub rounds: u32,
+    /// Round length in seconds, `0` for no time limit.
+    pub round_time: u32,
+    pub damage_multiplier: f32,
+    /// Per-player multiplier on starting health, indexed by `Player::id - 1`.
+    pub handicap: [f32; 2],
+    /// Whether chip damage from blocked hits can finish a fighter.
+    pub chip_ko: bool,
+    /// Whether low attacks can hit a knocked-down fighter.
+    pub otg: bool,
+}
+
+impl Default for MatchConfig {
+    fn default() -> Self {
+        Self {
+            starting_health: 100.,
+            rounds: 3,
+            round_time: 60,
+            damage_multiplier: 1.,
+            handicap: [1., 1.],
+            chip_ko: false,
+            otg: true,
+        }
+    }
+}
+
+impl MatchConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        ron::from_str(&text)
+            .map_err(|err| format!("invalid match config {}: {err}", path.display()))
+    }
+
+    pub fn rounds_to_win(&self) -> u32 {
+        self.rounds / 2 + 1
+    }
+
+    pub fn round_frames(&self) -> Option<u32> {
+        (self.round_time > 0).then(|| self.round_time * TICK_RATE as u32)
+    }
+
+    pub fn max_health(&self, player_id: usize) -> f32 {
+        let handicap = self.handicap.get(player_id - 1).copied().unwrap_or(1.);
+        self.starting_health * handicap
+    }
+}
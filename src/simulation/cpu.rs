@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use super::fighter::{Player, Players};
+use super::input::{Action, FighterInput, InputPhase};
+use super::replay::ReplayPlayback;
+use super::rng::SimRng;
+use super::stage::StageCollider;
+use super::state::FighterState;
+use super::{ControlBasis, RoundState};
+
+pub const MAX_CPU_LEVEL: u8 = 9;
+const CPU_ATTACK_RANGE: f32 = 110.;
+/// Extra distance kept from stage geometry when sidestepping around it.
+const CPU_CLEARANCE: f32 = 40.;
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CpuController {
+    pub level: u8,
+}
+
+#[derive(Resource, Default)]
+pub struct CpuSettings {
+    /// CPU difficulty per player, indexed by `Player::id - 1`; `None` for a human.
+    pub levels: [Option<u8>; 2],
+}
+
+pub struct CpuPlugin;
+
+impl Plugin for CpuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CpuSettings>()
+            .add_systems(PostStartup, attach_cpu_controllers)
+            .add_systems(
+                FixedUpdate,
+                cpu_input_system
+                    .in_set(InputPhase::Collect)
+                    .run_if(in_state(RoundState::Fighting))
+                    .run_if(not(resource_exists::<ReplayPlayback>())),
+            );
+    }
+}
+
+fn attach_cpu_controllers(
+    mut commands: Commands,
+    settings: Res<CpuSettings>,
+    fighters: Query<(Entity, &Player)>,
+) {
+    for (entity, player) in fighters.iter() {
+        if let Some(Some(level)) = settings.levels.get(player.id - 1) {
+            commands.entity(entity).insert(CpuController {
+                level: (*level).clamp(1, MAX_CPU_LEVEL),
+            });
+        }
+    }
+}
+
+fn cpu_input_system(
+    players: Res<Players>,
+    basis: Res<ControlBasis>,
+    mut rng: ResMut<SimRng>,
+    transforms: Query<&Transform>,
+    colliders: Query<(&Transform, &StageCollider)>,
+    mut cpus: Query<(Entity, &CpuController, &FighterState, &mut FighterInput)>,
+) {
+    for (entity, cpu, state, mut input) in cpus.iter_mut() {
+        let opponent = if entity == players.player1 {
+            players.player2
+        } else {
+            players.player1
+        };
+        let Ok([own, other]) = transforms.get_many([entity, opponent]) else {
+            continue;
+        };
+        let offset = other.translation - own.translation;
+        let distance = Vec3::new(offset.x, 0.0, offset.z).length();
+        let skill = cpu.level as f32 / MAX_CPU_LEVEL as f32;
+
+        input.held = 0;
+        if !state.can_act() {
+            continue;
+        }
+        let toward = if offset.dot(basis.right) >= 0.0 {
+            Action::Right
+        } else {
+            Action::Left
+        };
+        if distance > CPU_ATTACK_RANGE * 0.8 {
+            input.held |= 1 << toward as u8;
+        }
+        let direction = Vec3::new(offset.x, 0.0, offset.z).normalize_or_zero();
+        let obstacle = colliders.iter().find(|(block, collider)| {
+            let to_block = block.translation - own.translation;
+            let along = to_block.dot(direction);
+            let across = to_block.dot(basis.forward).abs();
+            along > 0.0
+                && along < distance
+                && across < collider.half_extents.x.max(collider.half_extents.z) + CPU_CLEARANCE
+        });
+        if let Some((block, _)) = obstacle {
+            let sidestep = if (own.translation - block.translation).dot(basis.forward) >= 0.0 {
+                Action::Up
+            } else {
+                Action::Down
+            };
+            input.held |= 1 << sidestep as u8;
+        }
+        if distance <= CPU_ATTACK_RANGE && rng.chance(0.02 + 0.1 * skill) {
+            let attack = if distance > 85. || rng.chance(0.3) {
+                Action::Kick
+            } else {
+                Action::Punch
+            };
+            input.pressed |= 1 << attack as u8;
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::collision::{BodyRegion, Hurtbox, Hurtboxes, Shape};
+use super::combat::AttackKind;
+use super::GameSet;
+
+pub const DEFAULT_FIGHTER_PATH: &str = "fighters/default.fighter.ron";
+/// The shipped default fighter, built in for when no definition can be loaded from disk.
+const BUNDLED_FIGHTER: &str = include_str!("../../assets/fighters/default.fighter.ron");
+
+#[derive(TypeUuid, TypePath, Clone, Debug, Serialize, Deserialize)]
+#[uuid = "afd2e72b-f022-4f79-aaaa-2c33d6ffdf55"]
+pub struct FighterDefinition {
+    #[serde(default)]
+    pub name: String,
+    /// Scene to render the fighter with, relative to the assets directory.
+    #[serde(default)]
+    pub model: Option<String>,
+    pub stats: FighterStats,
+    pub pushbox: Pushbox,
+    #[serde(default)]
+    pub hurtboxes: Vec<Hurtbox>,
+    pub region_damage: RegionDamage,
+    #[serde(default)]
+    pub moves: HashMap<AttackKind, MoveData>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FighterStats {
+    pub walk_speed: f32,
+    pub dash_speed: f32,
+    pub dash_frames: u32,
+    /// Frames allowed between the two taps of a dash.
+    pub dash_window: u32,
+    pub jump_velocity: f32,
+    /// Horizontal speed of a forward or back jump.
+    pub jump_speed: f32,
+    pub takeoff_frames: u32,
+    pub landing_frames: u32,
+    /// Horizontal acceleration towards the walk speed while grounded, in units/s².
+    pub acceleration: f32,
+    /// Horizontal deceleration while grounded and not walking, in units/s².
+    pub friction: f32,
+    /// Fraction of horizontal speed lost per second while airborne.
+    pub air_drag: f32,
+    /// Horizontal acceleration from steering while airborne, in units/s².
+    pub air_control: f32,
+    /// Extra jumps allowed before landing again.
+    pub air_jumps: u32,
+    pub air_jump_velocity: f32,
+    /// Air dashes allowed before landing again.
+    pub air_dashes: u32,
+    pub air_dash_speed: f32,
+    pub air_dash_frames: u32,
+    /// Jumps off the arena bounds allowed before landing again.
+    pub wall_jumps: u32,
+    /// Horizontal speed away from the wall after a wall jump.
+    pub wall_jump_speed: f32,
+    /// Frames spent on the floor after a knockdown before waking up.
+    pub knockdown_frames: u32,
+    /// Frames after hitting the floor in which Jump performs a tech roll.
+    pub tech_window: u32,
+    pub roll_speed: f32,
+    pub roll_frames: u32,
+}
+
+/// Upright cylinder standing on the fighter's origin that keeps fighters and walls apart.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Pushbox {
+    pub radius: f32,
+    pub height: f32,
+}
+
+/// Damage multiplier for hits landing on each body region.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegionDamage {
+    pub head: f32,
+    pub torso: f32,
+    pub legs: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoveData {
+    pub damage: f32,
+    pub startup: u32,
+    pub active: u32,
+    pub recovery: u32,
+    pub hitbox: Shape,
+    /// Centre of the hitbox relative to the attacker facing +X.
+    pub offset: Vec3,
+    /// How many times one target can be struck by a single use of the move.
+    #[serde(default = "default_hits")]
+    pub hits: u32,
+    /// Minimum frames between two hits on the same target.
+    #[serde(default = "default_hit_interval")]
+    pub hit_interval: u32,
+    /// Recovery frames when an aerial move is still going as the attacker lands.
+    #[serde(default)]
+    pub landing_recovery: u32,
+    /// Frames the defender cannot act after being hit.
+    #[serde(default)]
+    pub hitstun: u32,
+    /// Velocity given to the defender on hit, relative to the attacker facing +X.
+    #[serde(default)]
+    pub knockback: Vec3,
+    /// Whether a clean hit puts the defender on the floor.
+    #[serde(default)]
+    pub knockdown: bool,
+    /// Whether a clean hit pops the defender up into a juggle.
+    #[serde(default)]
+    pub launcher: bool,
+    /// Whether the move hits low enough to strike a knocked-down fighter.
+    #[serde(default)]
+    pub low: bool,
+    /// Damage dealt through a block, before the match damage multiplier.
+    #[serde(default)]
+    pub chip_damage: f32,
+    #[serde(default)]
+    pub blockstun: u32,
+    /// Speed at which a blocked hit pushes the defender away.
+    #[serde(default)]
+    pub pushback: f32,
+}
+
+fn default_hits() -> u32 {
+    1
+}
+
+fn default_hit_interval() -> u32 {
+    1
+}
+
+impl Default for FighterDefinition {
+    fn default() -> Self {
+        ron::from_str(BUNDLED_FIGHTER).expect("the bundled fighter definition is valid")
+    }
+}
+
+impl RegionDamage {
+    pub fn multiplier(&self, region: BodyRegion) -> f32 {
+        match region {
+            BodyRegion::Head => self.head,
+            BodyRegion::Torso => self.torso,
+            BodyRegion::Legs => self.legs,
+        }
+    }
+}
+
+impl FighterDefinition {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        ron::from_str(&text)
+            .map_err(|err| format!("invalid fighter definition {}: {err}", path.display()))
+    }
+
+    pub fn move_data(&self, kind: AttackKind) -> Option<&MoveData> {
+        self.moves.get(&kind)
+    }
+}
+
+/// The definition a fighter currently plays with.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+pub struct Fighter(pub FighterDefinition);
+
+/// The asset a [`Fighter`] was loaded from, used to apply hot reloads.
+#[derive(Component, Clone, Debug)]
+pub struct FighterSource(pub Handle<FighterDefinition>);
+
+/// Definition files for each player, relative to the assets directory.
+#[derive(Resource, Clone, Debug)]
+pub struct FighterRoster {
+    pub paths: [String; 2],
+}
+
+impl Default for FighterRoster {
+    fn default() -> Self {
+        Self {
+            paths: [
+                DEFAULT_FIGHTER_PATH.to_string(),
+                DEFAULT_FIGHTER_PATH.to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FighterDefinitionLoader;
+
+impl AssetLoader for FighterDefinitionLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let definition: FighterDefinition = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(definition));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["fighter.ron"]
+    }
+}
+
+pub struct FighterDefinitionPlugin;
+
+impl Plugin for FighterDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FighterRoster>();
+        // Without an asset server (headless runs) definitions are read straight from disk.
+        if app.world.contains_resource::<AssetServer>() {
+            app.add_asset::<FighterDefinition>()
+                .init_asset_loader::<FighterDefinitionLoader>()
+                .add_systems(
+                    Update,
+                    sync_fighter_definitions_system.in_set(GameSet::Input),
+                );
+        }
+    }
+}
+
+fn sync_fighter_definitions_system(
+    mut events: EventReader<AssetEvent<FighterDefinition>>,
+    definitions: Res<Assets<FighterDefinition>>,
+    mut fighters: Query<(&FighterSource, &mut Fighter, &mut Hurtboxes)>,
+) {
+    for event in events.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = event else {
+            continue;
+        };
+        let Some(definition) = definitions.get(handle) else {
+            continue;
+        };
+        for (source, mut fighter, mut hurtboxes) in fighters.iter_mut() {
+            if source.0 == *handle {
+                fighter.0 = definition.clone();
+                hurtboxes.0 = definition.hurtboxes.clone();
+            }
+        }
+        info!("loaded fighter definition {}", definition.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_fighter_has_every_move() {
+        let definition = FighterDefinition::default();
+        for kind in [
+            AttackKind::Punch,
+            AttackKind::Kick,
+            AttackKind::JumpKick,
+            AttackKind::Uppercut,
+            AttackKind::WakeUpKick,
+        ] {
+            assert!(definition.move_data(kind).is_some(), "{kind:?}");
+        }
+        assert!(!definition.hurtboxes.is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use super::collision::BodyRegion;
+use super::combat::AttackKind;
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HitEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub attack: AttackKind,
+    pub damage: f32,
+    pub region: BodyRegion,
+    pub position: Vec3,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct BlockEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub attack: AttackKind,
+    pub damage: f32,
+    pub position: Vec3,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct WhiffEvent {
+    pub attacker: Entity,
+    pub attack: AttackKind,
+    pub position: Vec3,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct KoEvent {
+    pub attacker: Entity,
+    pub defender: Entity,
+    pub attack: AttackKind,
+    pub damage: f32,
+    pub position: Vec3,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RoundEndEvent {
+    pub round: u32,
+    pub winner: Option<usize>,
+    pub wins: [u32; 2],
+}
+
+pub struct CombatEventsPlugin;
+
+impl Plugin for CombatEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<HitEvent>()
+            .add_event::<BlockEvent>()
+            .add_event::<WhiffEvent>()
+            .add_event::<KoEvent>()
+            .add_event::<RoundEndEvent>();
+    }
+}
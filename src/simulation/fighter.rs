@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use std::path::Path;
+
+use super::collision::{Hurtboxes, PreviousTransform};
+use super::combat::{AttackAnimationState, Combo, Juggle};
+use super::config::MatchConfig;
+use super::definition::{Fighter, FighterDefinition, FighterRoster, FighterSource};
+use super::input::FighterInput;
+use super::movement::{AirActions, MoveIntent, SlideState};
+use super::state::FighterState;
+
+#[derive(Component)]
+pub struct GameEntity;
+
+#[derive(Component, Clone)]
+pub struct Player {
+    pub id: usize,
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Velocity(pub Vec3);
+
+#[derive(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+#[derive(Component)]
+pub struct SpawnPoint(pub Vec3);
+
+#[derive(Resource)]
+pub struct Players {
+    pub player1: Entity,
+    pub player2: Entity,
+}
+
+pub struct FighterPlugin;
+
+impl Plugin for FighterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_fighters);
+    }
+}
+
+fn spawn_fighters(
+    mut commands: Commands,
+    config: Res<MatchConfig>,
+    roster: Res<FighterRoster>,
+    asset_server: Option<Res<AssetServer>>,
+) {
+    let mut spawn_player = |id: usize, x: f32| {
+        let spawn = Vec3::new(x, 0., 0.);
+        let health = config.max_health(id);
+        let path = &roster.paths[id - 1];
+        let definition = match &asset_server {
+            Some(_) => FighterDefinition::default(),
+            None => FighterDefinition::load(Path::new("assets").join(path)).unwrap_or_else(|err| {
+                warn!("{err}, using the default fighter");
+                FighterDefinition::default()
+            }),
+        };
+        let mut fighter = commands.spawn((
+            TransformBundle::from_transform(Transform::from_translation(spawn)),
+            Player { id },
+            SpawnPoint(spawn),
+            Velocity(Vec3::ZERO),
+            FighterState::Idle,
+            Health {
+                current: health,
+                max: health,
+            },
+            FighterInput::default(),
+            (
+                SlideState::default(),
+                MoveIntent::default(),
+                AirActions::default(),
+            ),
+            (
+                AttackAnimationState::default(),
+                Juggle::default(),
+                Combo::default(),
+            ),
+            PreviousTransform(Transform::from_translation(spawn)),
+            Hurtboxes(definition.hurtboxes.clone()),
+            Fighter(definition),
+            GameEntity,
+        ));
+        if let Some(asset_server) = &asset_server {
+            fighter.insert(FighterSource(asset_server.load(path.as_str())));
+        }
+        fighter.id()
+    };
+
+    let player1 = spawn_player(1, 100.);
+    let player2 = spawn_player(2, 700.);
+
+    commands.insert_resource(Players { player1, player2 });
+}
@@ -0,0 +1,355 @@
+use bevy::prelude::*;
+
+use super::combat::{AttackAnimationState, AttackKind};
+use super::definition::Fighter;
+use super::fighter::{Player, Players, Velocity};
+use super::movement::{AirActions, MoveIntent, SlideState};
+use super::state::FighterState;
+use super::{GameSet, RoundState, SimFrame};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Left,
+    Right,
+    Up,
+    Down,
+    Punch,
+    Kick,
+    Jump,
+}
+
+#[derive(Component, Default, Clone, Copy)]
+pub struct FighterInput {
+    pub held: u8,
+    pub pressed: u8,
+}
+
+impl FighterInput {
+    pub fn held(&self, action: Action) -> bool {
+        self.held & (1 << action as u8) != 0
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.pressed & (1 << action as u8) != 0
+    }
+}
+
+#[derive(Resource)]
+pub struct ControlBasis {
+    pub right: Vec3,
+    pub forward: Vec3,
+}
+
+impl Default for ControlBasis {
+    fn default() -> Self {
+        Self {
+            right: Vec3::X,
+            forward: Vec3::NEG_Z,
+        }
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputPhase {
+    Collect,
+    Record,
+    Apply,
+}
+
+#[derive(Resource, Default)]
+pub struct PlayerInputMemory {
+    pub last_press: std::collections::HashMap<(usize, Action), u64>,
+}
+
+pub struct FighterInputPlugin;
+
+impl Plugin for FighterInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ControlBasis::default())
+            .insert_resource(PlayerInputMemory::default())
+            .configure_sets(
+                FixedUpdate,
+                (InputPhase::Collect, InputPhase::Record, InputPhase::Apply)
+                    .chain()
+                    .in_set(GameSet::Input),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    // Collectors such as the CPU read the basis, so it settles first.
+                    update_control_basis_system
+                        .in_set(GameSet::Input)
+                        .before(InputPhase::Collect),
+                    player_input_system
+                        .in_set(InputPhase::Apply)
+                        .run_if(in_state(RoundState::Fighting)),
+                ),
+            );
+    }
+}
+
+fn update_control_basis_system(
+    players: Option<Res<Players>>,
+    transforms: Query<&Transform>,
+    mut basis: ResMut<ControlBasis>,
+) {
+    let Some(players) = players else {
+        return;
+    };
+    let Ok([t1, t2]) = transforms.get_many([players.player1, players.player2]) else {
+        return;
+    };
+    let axis = t2.translation - t1.translation;
+    let axis = Vec3::new(axis.x, 0.0, axis.z).normalize_or_zero();
+    if axis == Vec3::ZERO {
+        return;
+    }
+    basis.right = axis;
+    basis.forward = Vec3::new(axis.z, 0.0, -axis.x);
+}
+
+fn player_input_system(
+    mut query: Query<(
+        &Player,
+        &mut FighterInput,
+        &mut Velocity,
+        &mut MoveIntent,
+        &mut AirActions,
+        &mut FighterState,
+        &mut SlideState,
+        &mut AttackAnimationState,
+        &Transform,
+        &Fighter,
+    )>,
+    basis: Res<ControlBasis>,
+    mut input_memory: ResMut<PlayerInputMemory>,
+    frame: Res<SimFrame>,
+) {
+    let now = **frame;
+
+    let right = Vec3::new(basis.right.x, 0.0, basis.right.z).normalize_or_zero();
+    let forward = Vec3::new(basis.forward.x, 0.0, basis.forward.z).normalize_or_zero();
+
+    for (
+        player,
+        mut input,
+        mut vel,
+        mut intent,
+        mut air,
+        mut state,
+        mut slide,
+        mut attack_anim,
+        transform,
+        fighter,
+    ) in query.iter_mut()
+    {
+        let stats = &fighter.stats;
+        let airborne = state.is_airborne() || transform.translation.y > 0.0;
+        let mut dir = Vec3::ZERO;
+        let mut slide_dir = Vec3::ZERO;
+        let mut slide_action: Option<Action> = None;
+
+        let mut input_dir = Vec3::ZERO;
+        if input.held(Action::Left) {
+            input_dir -= right;
+            slide_dir = -right;
+            slide_action = Some(Action::Left);
+        }
+        if input.held(Action::Right) {
+            input_dir += right;
+            slide_dir = right;
+            slide_action = Some(Action::Right);
+        }
+        if input.held(Action::Up) {
+            input_dir += forward;
+            slide_dir = forward;
+            slide_action = Some(Action::Up);
+        }
+        if input.held(Action::Down) {
+            input_dir -= forward;
+            slide_dir = -forward;
+            slide_action = Some(Action::Down);
+        }
+        if input_dir.length_squared() > 0. {
+            dir = input_dir.normalize();
+        }
+
+        if let Some(action) = slide_action {
+            if input.just_pressed(action) {
+                let last = input_memory.last_press.get(&(player.id, action)).copied();
+                let dash = if !airborne {
+                    Some(FighterState::Dash {
+                        frames_left: stats.dash_frames,
+                    })
+                } else if air.dashes < stats.air_dashes {
+                    Some(FighterState::AirDash {
+                        frames_left: stats.air_dash_frames,
+                    })
+                } else {
+                    None
+                };
+                if last.is_some_and(|last| now - last < stats.dash_window as u64)
+                    && dash.is_some_and(|dash| state.transition(dash))
+                {
+                    slide.direction = slide_dir.normalize_or_zero();
+                    if airborne {
+                        air.dashes += 1;
+                    }
+                }
+                input_memory.last_press.insert((player.id, action), now);
+            }
+        }
+
+        if input.just_pressed(Action::Jump) {
+            if !airborne {
+                state.transition(FighterState::Takeoff {
+                    frames_left: stats.takeoff_frames,
+                });
+            } else if state.is_airborne() {
+                if let Some(wall) = air.wall.filter(|_| air.wall_jumps < stats.wall_jumps) {
+                    air.wall_jumps += 1;
+                    **intent = wall * stats.wall_jump_speed;
+                    **vel = Vec3::new(intent.x, stats.jump_velocity, intent.z);
+                } else if air.jumps < stats.air_jumps {
+                    air.jumps += 1;
+                    **intent = jump_direction(dir, right) * stats.jump_speed;
+                    **vel = Vec3::new(intent.x, stats.air_jump_velocity, intent.z);
+                }
+            }
+        }
+
+        let knocked_down = matches!(*state, FighterState::Knockdown { .. });
+        if let FighterState::Knockdown { frames_left } = *state {
+            let facing = transform.rotation * Vec3::X;
+            let back = -Vec3::new(facing.x, 0.0, facing.z).normalize_or_zero();
+            if frames_left + stats.tech_window > stats.knockdown_frames
+                && input.just_pressed(Action::Jump)
+            {
+                let direction = roll_direction(dir, forward, back).unwrap_or(back);
+                if state.transition(FighterState::Roll {
+                    frames_left: stats.roll_frames,
+                }) {
+                    slide.direction = direction;
+                }
+            } else if frames_left <= 1 {
+                // Wake-up options are read from the buttons held on the last frame down.
+                if input.held(Action::Punch) || input.held(Action::Kick) {
+                    start_attack(
+                        AttackKind::WakeUpKick,
+                        fighter,
+                        &mut state,
+                        &mut attack_anim,
+                        transform,
+                    );
+                } else if let Some(direction) = roll_direction(dir, forward, back) {
+                    if state.transition(FighterState::Roll {
+                        frames_left: stats.roll_frames,
+                    }) {
+                        slide.direction = direction;
+                    }
+                }
+            }
+        }
+
+        if let Some(kind) = pressed_attack(&input, airborne).filter(|_| !knocked_down) {
+            start_attack(kind, fighter, &mut state, &mut attack_anim, transform);
+        }
+
+        match *state {
+            FighterState::Dash { .. } => {
+                **intent = slide.direction * stats.dash_speed;
+                vel.x = intent.x;
+                vel.z = intent.z;
+            }
+            FighterState::Idle | FighterState::Walk => {
+                **intent = dir * stats.walk_speed;
+                let next = if dir == Vec3::ZERO {
+                    FighterState::Idle
+                } else {
+                    FighterState::Walk
+                };
+                state.transition(next);
+            }
+            FighterState::Takeoff { frames_left: 0 } => {
+                **intent = jump_direction(dir, right) * stats.jump_speed;
+                **vel = Vec3::new(intent.x, stats.jump_velocity, intent.z);
+                state.transition(FighterState::Airborne);
+            }
+            FighterState::Roll { .. } => {
+                **intent = slide.direction * stats.roll_speed;
+                vel.x = intent.x;
+                vel.z = intent.z;
+            }
+            FighterState::AirDash { .. } => {
+                **intent = slide.direction * stats.air_dash_speed;
+                **vel = **intent;
+            }
+            FighterState::Airborne => {
+                **intent = dir * stats.jump_speed;
+            }
+            _ => {
+                **intent = Vec3::ZERO;
+            }
+        }
+
+        input.pressed = 0;
+    }
+}
+
+fn start_attack(
+    kind: AttackKind,
+    fighter: &Fighter,
+    state: &mut FighterState,
+    anim: &mut AttackAnimationState,
+    transform: &Transform,
+) {
+    let Some(data) = fighter.move_data(kind) else {
+        return;
+    };
+    if state.transition(FighterState::Startup {
+        frames_left: data.startup,
+    }) {
+        anim.kind = Some(kind);
+        anim.connected = false;
+        anim.end_pos = transform.translation + transform.rotation * data.offset;
+    }
+}
+
+/// Holding up or down rolls sideways and holding back rolls away; `None` stays in place.
+fn roll_direction(dir: Vec3, forward: Vec3, back: Vec3) -> Option<Vec3> {
+    let side = dir.dot(forward);
+    if side.abs() > f32::EPSILON {
+        Some(forward * side.signum())
+    } else if dir.dot(back) > f32::EPSILON {
+        Some(back)
+    } else {
+        None
+    }
+}
+
+/// Holding towards or away from the opponent picks a forward or back jump.
+fn jump_direction(dir: Vec3, right: Vec3) -> Vec3 {
+    let along = dir.dot(right);
+    if along.abs() > f32::EPSILON {
+        right * along.signum()
+    } else {
+        Vec3::ZERO
+    }
+}
+
+fn pressed_attack(input: &FighterInput, airborne: bool) -> Option<AttackKind> {
+    if airborne {
+        return input
+            .just_pressed(Action::Kick)
+            .then_some(AttackKind::JumpKick);
+    }
+    if input.just_pressed(Action::Punch) && input.held(Action::Down) {
+        Some(AttackKind::Uppercut)
+    } else if input.just_pressed(Action::Punch) {
+        Some(AttackKind::Punch)
+    } else if input.just_pressed(Action::Kick) {
+        Some(AttackKind::Kick)
+    } else {
+        None
+    }
+}
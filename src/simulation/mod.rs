@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+pub mod broad_phase;
+pub mod collision;
+pub mod combat;
+pub mod config;
+pub mod cpu;
+pub mod definition;
+pub mod events;
+pub mod fighter;
+pub mod input;
+pub mod movement;
+pub mod physics;
+pub mod replay;
+pub mod rig;
+pub mod rng;
+pub mod round;
+pub mod stage;
+pub mod state;
+
+pub use collision::{BodyRegion, Hurtbox, Hurtboxes, PreviousTransform, Shape};
+pub use combat::{AttackAnimationState, AttackKind, Combo, Hitbox, Juggle, Lifetime};
+pub use config::MatchConfig;
+pub use cpu::{CpuController, CpuSettings};
+pub use definition::{
+    Fighter, FighterDefinition, FighterRoster, FighterSource, MoveData, Pushbox, RegionDamage,
+};
+pub use events::{BlockEvent, HitEvent, KoEvent, RoundEndEvent, WhiffEvent};
+pub use fighter::{GameEntity, Health, Player, Players, SpawnPoint, Velocity};
+pub use input::{Action, ControlBasis, FighterInput, InputPhase, PlayerInputMemory};
+pub use movement::{AirActions, MoveIntent, SlideState};
+pub use replay::{Replay, ReplayPlayback, ReplayRecorder};
+pub use rig::{HurtboxRig, Joint, Track};
+pub use rng::SimRng;
+pub use round::{MatchScore, RoundClock, RoundState, RoundTimer};
+pub use stage::{Stage, StageCollider};
+pub use state::FighterState;
+
+pub const ARENA_WIDTH: f32 = 800.;
+pub const ARENA_DEPTH: f32 = 400.;
+pub const TICK_RATE: f32 = 60.;
+pub const DT: f32 = 1. / TICK_RATE;
+
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Input,
+    Movement,
+    Physics,
+    Combat,
+    Round,
+    Presentation,
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct SimFrame(pub u64);
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MatchConfig>()
+            .init_resource::<Stage>()
+            .init_resource::<SimRng>()
+            .insert_resource(SimFrame::default())
+            .insert_resource(FixedTime::new_from_secs(DT))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    GameSet::Input,
+                    GameSet::Movement,
+                    GameSet::Physics,
+                    GameSet::Combat,
+                    GameSet::Round,
+                )
+                    .chain(),
+            )
+            .configure_set(
+                FixedUpdate,
+                GameSet::Movement.run_if(in_state(RoundState::Fighting)),
+            )
+            .configure_set(
+                FixedUpdate,
+                GameSet::Physics.run_if(in_state(RoundState::Fighting)),
+            )
+            .configure_set(
+                FixedUpdate,
+                GameSet::Combat.run_if(in_state(RoundState::Fighting)),
+            )
+            .configure_sets(Update, (GameSet::Input, GameSet::Presentation).chain())
+            .add_systems(FixedUpdate, advance_frame_system.before(GameSet::Input))
+            .add_plugins((
+                events::CombatEventsPlugin,
+                collision::CollisionPlugin,
+                definition::FighterDefinitionPlugin,
+                fighter::FighterPlugin,
+                state::FighterStatePlugin,
+                input::FighterInputPlugin,
+                cpu::CpuPlugin,
+                replay::ReplayPlugin,
+                rig::RigPlugin,
+                movement::MovementPlugin,
+                physics::PhysicsPlugin,
+                combat::CombatPlugin,
+                round::RoundPlugin,
+                stage::StagePlugin,
+            ));
+    }
+}
+
+fn advance_frame_system(mut frame: ResMut<SimFrame>) {
+    **frame += 1;
+}
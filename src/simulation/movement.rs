@@ -0,0 +1,63 @@
+use bevy::ecs::system::ParamSet;
+use bevy::prelude::*;
+
+use super::fighter::Player;
+use super::GameSet;
+
+#[derive(Component, Default)]
+pub struct SlideState {
+    pub direction: Vec3,
+}
+
+/// Air movement spent since the fighter last stood on the ground.
+#[derive(Component, Default)]
+pub struct AirActions {
+    pub jumps: u32,
+    pub dashes: u32,
+    pub wall_jumps: u32,
+    /// Inward normal of the arena bound the fighter is touching while airborne.
+    pub wall: Option<Vec3>,
+}
+
+/// Horizontal velocity the fighter is steering towards this frame.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct MoveIntent(pub Vec3);
+
+pub struct MovementPlugin;
+
+impl Plugin for MovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            face_each_other_system
+                .after(GameSet::Physics)
+                .before(GameSet::Combat),
+        );
+    }
+}
+
+fn face_each_other_system(
+    mut param_set: ParamSet<(Query<(Entity, &Player, &Transform)>, Query<&mut Transform>)>,
+) {
+    let players: Vec<(Entity, Player, Transform)> = {
+        let query = param_set.p0();
+        query
+            .iter()
+            .map(|(entity, player, transform)| (entity, player.clone(), *transform))
+            .collect()
+    };
+    if players.len() != 2 {
+        return;
+    }
+    let (entity1, _p1, t1) = &players[0];
+    let (entity2, _p2, t2) = &players[1];
+    let mut binding = param_set.p1();
+    if let Ok([mut transform1, mut transform2]) = binding.get_many_mut([*entity1, *entity2]) {
+        let dir_1_to_2 = (t2.translation - t1.translation).normalize_or_zero();
+        let dir_2_to_1 = -dir_1_to_2;
+        transform1.rotation =
+            Quat::from_rotation_arc(Vec3::X, Vec3::new(dir_1_to_2.x, 0.0, dir_1_to_2.z));
+        transform2.rotation =
+            Quat::from_rotation_arc(Vec3::X, Vec3::new(dir_2_to_1.x, 0.0, dir_2_to_1.z));
+    }
+}
@@ -0,0 +1,268 @@
+use bevy::prelude::*;
+
+use super::combat::{AttackAnimationState, Juggle};
+use super::definition::Fighter;
+use super::fighter::{Players, Velocity};
+use super::movement::{AirActions, MoveIntent};
+use super::stage::StageCollider;
+use super::state::FighterState;
+use super::{GameSet, ARENA_DEPTH, ARENA_WIDTH, DT};
+
+const GRAVITY: f32 = -1200.;
+/// Minimum falling speed at which a launched fighter bounces off the floor.
+const GROUND_BOUNCE_SPEED: f32 = 250.;
+/// Fraction of the falling speed kept by a ground bounce.
+const GROUND_BOUNCE_RESTITUTION: f32 = 0.4;
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                momentum_system,
+                apply_velocity_system,
+                ground_and_gravity_system,
+                push_fighters_apart_system,
+                stage_collision_system,
+            )
+                .chain()
+                .in_set(GameSet::Physics),
+        );
+    }
+}
+
+fn momentum_system(mut query: Query<(&mut Velocity, &MoveIntent, &Transform, &Fighter)>) {
+    for (mut velocity, intent, transform, fighter) in query.iter_mut() {
+        let stats = &fighter.stats;
+        let horizontal = Vec3::new(velocity.x, 0.0, velocity.z);
+        let target = Vec3::new(intent.x, 0.0, intent.z);
+        let horizontal = if transform.translation.y > 0.0 {
+            // Drag bleeds off momentum; steering can only add speed up to the intended speed.
+            let mut horizontal = horizontal * (1.0 - stats.air_drag * DT).max(0.0);
+            if let Some(direction) = target.try_normalize() {
+                let missing = target.length() - horizontal.dot(direction);
+                if missing > 0.0 {
+                    horizontal += direction * missing.min(stats.air_control * DT);
+                }
+            }
+            horizontal
+        } else {
+            let rate = if horizontal.length() > target.length() {
+                stats.friction
+            } else {
+                stats.acceleration
+            };
+            move_towards(horizontal, target, rate * DT)
+        };
+        velocity.x = horizontal.x;
+        velocity.z = horizontal.z;
+    }
+}
+
+fn move_towards(current: Vec3, target: Vec3, max_delta: f32) -> Vec3 {
+    let delta = target - current;
+    let distance = delta.length();
+    if distance <= max_delta {
+        target
+    } else {
+        current + delta / distance * max_delta
+    }
+}
+
+fn apply_velocity_system(
+    mut query: Query<(&mut Transform, &mut Velocity, Option<&mut AirActions>)>,
+) {
+    for (mut transform, mut velocity, air) in query.iter_mut() {
+        let moved = transform.translation + **velocity * DT;
+        transform.translation = clamp_to_arena(moved);
+        // The arena bounds act as walls: stop momentum into them and remember them for wall jumps.
+        let mut wall = None;
+        for (normal, touching) in [
+            (Vec3::X, moved.x <= 0.0),
+            (Vec3::NEG_X, moved.x >= ARENA_WIDTH),
+            (Vec3::Z, moved.z <= -ARENA_DEPTH / 2.0),
+            (Vec3::NEG_Z, moved.z >= ARENA_DEPTH / 2.0),
+        ] {
+            if touching {
+                let into_wall = velocity.dot(normal).min(0.0);
+                **velocity -= normal * into_wall;
+                wall = Some(normal);
+            }
+        }
+        if let Some(mut air) = air {
+            air.wall = wall.filter(|_| transform.translation.y > 0.0);
+        }
+    }
+}
+
+pub fn clamp_to_arena(position: Vec3) -> Vec3 {
+    Vec3::new(
+        position.x.clamp(0.0, ARENA_WIDTH),
+        position.y,
+        position.z.clamp(-ARENA_DEPTH / 2.0, ARENA_DEPTH / 2.0),
+    )
+}
+
+fn ground_and_gravity_system(
+    mut query: Query<(
+        &mut Velocity,
+        &mut FighterState,
+        &mut Transform,
+        &mut AttackAnimationState,
+        &mut AirActions,
+        &mut Juggle,
+        &Fighter,
+    )>,
+) {
+    for (mut velocity, mut state, mut transform, mut anim, mut air, mut juggle, fighter) in
+        query.iter_mut()
+    {
+        let air_dashing = matches!(*state, FighterState::AirDash { .. });
+        if transform.translation.y > 0.0 {
+            if air_dashing {
+                velocity.y = 0.0;
+            } else {
+                velocity.y += GRAVITY * juggle.gravity_scale() * DT;
+            }
+            // Grounded states leave the floor; air dashes end on their own.
+            if !state.is_airborne() && !air_dashing {
+                state.transition(FighterState::Airborne);
+            }
+            continue;
+        }
+        let impact = -velocity.y;
+        transform.translation.y = 0.0;
+        velocity.y = 0.0;
+        *air = AirActions::default();
+        *juggle = Juggle::default();
+        if matches!(*state, FighterState::Juggled { .. }) {
+            state.transition(FighterState::Knockdown {
+                frames_left: fighter.stats.knockdown_frames,
+            });
+            continue;
+        }
+        if let FighterState::Launched { bounced } = *state {
+            if !bounced && impact > GROUND_BOUNCE_SPEED {
+                velocity.y = impact * GROUND_BOUNCE_RESTITUTION;
+                state.transition(FighterState::Launched { bounced: true });
+            } else {
+                state.transition(FighterState::Knockdown {
+                    frames_left: fighter.stats.knockdown_frames,
+                });
+            }
+            continue;
+        }
+        if impact <= 0.0 {
+            continue;
+        }
+        if state.is_airborne() || state.can_act() || air_dashing {
+            state.transition(FighterState::Landing {
+                frames_left: fighter.stats.landing_frames,
+            });
+        } else if state.is_attacking() {
+            // Touching down ends an aerial move early with its own landing recovery.
+            let aerial = anim
+                .kind
+                .filter(|kind| kind.is_aerial())
+                .and_then(|kind| fighter.move_data(kind));
+            if let Some(data) = aerial {
+                state.transition(FighterState::Landing {
+                    frames_left: data.landing_recovery,
+                });
+                anim.kind = None;
+            }
+        }
+    }
+}
+
+fn push_fighters_apart_system(
+    players: Option<Res<Players>>,
+    mut fighters: Query<(&mut Transform, &Fighter)>,
+) {
+    let Some(players) = players else {
+        return;
+    };
+    let Ok([(mut t1, f1), (mut t2, f2)]) =
+        fighters.get_many_mut([players.player1, players.player2])
+    else {
+        return;
+    };
+    let (a, b) = (t1.translation, t2.translation);
+    if a.y > b.y + f2.pushbox.height || b.y > a.y + f1.pushbox.height {
+        return;
+    }
+    let min_distance = f1.pushbox.radius + f2.pushbox.radius;
+    let offset = Vec3::new(b.x - a.x, 0.0, b.z - a.z);
+    let overlap = min_distance - offset.length();
+    if overlap <= 0.0 {
+        return;
+    }
+    // Fighters standing on the same spot separate along the arena, player 1 to the left.
+    let axis = offset.try_normalize().unwrap_or(Vec3::X);
+    let half = axis * overlap / 2.0;
+    let (pushed_a, pushed_b) = (clamp_to_arena(a - half), clamp_to_arena(b + half));
+    // A fighter pinned against the arena edge cannot give way, so the other takes the rest.
+    let rest = axis
+        * (min_distance
+            - Vec3::new(pushed_b.x - pushed_a.x, 0.0, pushed_b.z - pushed_a.z).dot(axis));
+    let (pushed_a, pushed_b) = if pushed_a != a - half {
+        (pushed_a, clamp_to_arena(pushed_b + rest))
+    } else if pushed_b != b + half {
+        (clamp_to_arena(pushed_a - rest), pushed_b)
+    } else {
+        (pushed_a, pushed_b)
+    };
+    t1.translation = pushed_a;
+    t2.translation = pushed_b;
+}
+
+fn stage_collision_system(
+    colliders: Query<(&Transform, &StageCollider), Without<Fighter>>,
+    mut fighters: Query<(&mut Transform, &mut Velocity, &Fighter)>,
+) {
+    for (mut transform, mut velocity, fighter) in fighters.iter_mut() {
+        let radius = fighter.pushbox.radius;
+        for (block, collider) in colliders.iter() {
+            let min = block.translation - collider.half_extents;
+            let max = block.translation + collider.half_extents;
+            let position = transform.translation;
+            if position.y >= max.y || position.y + fighter.pushbox.height <= min.y {
+                continue;
+            }
+            let closest = Vec3::new(
+                position.x.clamp(min.x, max.x),
+                position.y,
+                position.z.clamp(min.z, max.z),
+            );
+            let offset = position - closest;
+            let distance = offset.length();
+            let normal = if distance > 0.0 {
+                if distance >= radius {
+                    continue;
+                }
+                transform.translation = closest + offset / distance * radius;
+                offset / distance
+            } else {
+                // The centre is inside the block: leave through the nearest face.
+                let exits = [
+                    (position.x - min.x, Vec3::NEG_X),
+                    (max.x - position.x, Vec3::X),
+                    (position.z - min.z, Vec3::NEG_Z),
+                    (max.z - position.z, Vec3::Z),
+                ];
+                let (depth, normal) = exits
+                    .into_iter()
+                    .min_by(|a, b| a.0.total_cmp(&b.0))
+                    .unwrap();
+                transform.translation += normal * (depth + radius);
+                normal
+            };
+            let into_block = velocity.dot(normal);
+            if into_block < 0.0 {
+                **velocity -= normal * into_block;
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::config::MatchConfig;
+use super::fighter::Players;
+use super::input::{FighterInput, InputPhase};
+use super::stage::Stage;
+use super::{RoundState, SimFrame};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub stage: Stage,
+    pub config: MatchConfig,
+    /// `(held, pressed)` input bits of both players for every simulation frame.
+    pub frames: Vec<[(u8, u8); 2]>,
+}
+
+impl Replay {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        ron::from_str(&text).map_err(|err| format!("invalid replay {}: {err}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = ron::to_string(self).map_err(|err| format!("cannot encode replay: {err}"))?;
+        std::fs::write(path, text).map_err(|err| format!("cannot write {}: {err}", path.display()))
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback(pub Replay);
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                playback_input_system
+                    .in_set(InputPhase::Collect)
+                    .run_if(resource_exists::<ReplayPlayback>()),
+                record_input_system
+                    .in_set(InputPhase::Record)
+                    .run_if(resource_exists::<ReplayRecorder>()),
+            ),
+        )
+        .add_systems(
+            OnEnter(RoundState::MatchOver),
+            save_recording_system.run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .add_systems(
+            Last,
+            save_recording_system
+                .run_if(resource_exists::<ReplayRecorder>())
+                .run_if(on_event::<AppExit>()),
+        );
+    }
+}
+
+fn playback_input_system(
+    playback: Res<ReplayPlayback>,
+    frame: Res<SimFrame>,
+    players: Res<Players>,
+    mut inputs: Query<&mut FighterInput>,
+) {
+    let recorded = playback
+        .0
+        .frames
+        .get(frame.0.saturating_sub(1) as usize)
+        .copied()
+        .unwrap_or_default();
+    for (entity, (held, pressed)) in [players.player1, players.player2].into_iter().zip(recorded) {
+        if let Ok(mut input) = inputs.get_mut(entity) {
+            input.held = held;
+            input.pressed = pressed;
+        }
+    }
+}
+
+fn record_input_system(
+    mut recorder: ResMut<ReplayRecorder>,
+    players: Res<Players>,
+    inputs: Query<&FighterInput>,
+) {
+    let Ok([p1, p2]) = inputs.get_many([players.player1, players.player2]) else {
+        return;
+    };
+    recorder
+        .replay
+        .frames
+        .push([(p1.held, p1.pressed), (p2.held, p2.pressed)]);
+}
+
+pub fn save_recording_system(recorder: Res<ReplayRecorder>) {
+    match recorder.replay.save(&recorder.path) {
+        Ok(()) => info!(
+            "saved {} frames to {}",
+            recorder.replay.frames.len(),
+            recorder.path.display()
+        ),
+        Err(err) => error!("{err}"),
+    }
+}
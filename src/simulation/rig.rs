@@ -0,0 +1,173 @@
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::collision::Hurtboxes;
+use super::{GameSet, SimFrame, DT};
+
+/// Keyframes of one transform channel, sampled with linear interpolation.
+#[derive(Clone, Debug)]
+pub struct Track<T> {
+    pub times: Vec<f32>,
+    pub values: Vec<T>,
+}
+
+impl<T: Copy> Track<T> {
+    /// Holds the first and last keyframes outside the track's time range.
+    fn sample(&self, time: f32, lerp: impl Fn(T, T, f32) -> T) -> T {
+        let next = self.times.partition_point(|&t| t <= time);
+        if next == 0 {
+            return self.values[0];
+        }
+        if next == self.times.len() {
+            return self.values[next - 1];
+        }
+        let (start, end) = (self.times[next - 1], self.times[next]);
+        lerp(
+            self.values[next - 1],
+            self.values[next],
+            (time - start) / (end - start),
+        )
+    }
+}
+
+/// One node of a model's hierarchy: its resting local transform and any animated channels.
+#[derive(Clone, Debug, Default)]
+pub struct Joint {
+    pub rest: Transform,
+    pub translation: Option<Track<Vec3>>,
+    pub rotation: Option<Track<Quat>>,
+    pub scale: Option<Track<Vec3>>,
+}
+
+impl Joint {
+    fn local_transform(&self, time: f32) -> Transform {
+        let mut transform = self.rest;
+        if let Some(track) = &self.translation {
+            transform.translation = track.sample(time, Vec3::lerp);
+        }
+        if let Some(track) = &self.rotation {
+            transform.rotation = track.sample(time, |start, end, s| {
+                // Take the short way round, as the renderer does.
+                let end = if end.dot(start) < 0.0 { -end } else { end };
+                start.normalize().slerp(end.normalize(), s)
+            });
+        }
+        if let Some(track) = &self.scale {
+            transform.scale = track.sample(time, Vec3::lerp);
+        }
+        transform
+    }
+}
+
+/// Animated skeleton a fighter's hurtboxes follow, posed from the simulation frame so that
+/// hits land where the model's limbs are without depending on the render clock.
+#[derive(Component, Clone, Debug, Default)]
+pub struct HurtboxRig {
+    /// Length of the looping clip in seconds.
+    pub duration: f32,
+    /// Joints from just below the fighter down to each followed bone, by bone name.
+    pub bones: HashMap<String, Vec<Joint>>,
+}
+
+impl HurtboxRig {
+    /// Point in the looping clip reached at a simulation frame.
+    pub fn clip_time(&self, frame: u64) -> f32 {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        (frame as f64 * DT as f64).rem_euclid(self.duration as f64) as f32
+    }
+
+    /// Position of a bone relative to the fighter, or `None` when the rig does not have it.
+    pub fn bone_offset(&self, bone: &str, time: f32) -> Option<Vec3> {
+        let joints = self.bones.get(bone)?;
+        let to_fighter = joints.iter().fold(Affine3A::IDENTITY, |to_fighter, joint| {
+            to_fighter * joint.local_transform(time).compute_affine()
+        });
+        Some(to_fighter.translation.into())
+    }
+}
+
+pub struct RigPlugin;
+
+impl Plugin for RigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, pose_hurtboxes_system.in_set(GameSet::Movement));
+    }
+}
+
+fn pose_hurtboxes_system(frame: Res<SimFrame>, mut fighters: Query<(&HurtboxRig, &mut Hurtboxes)>) {
+    for (rig, mut hurtboxes) in fighters.iter_mut() {
+        let time = rig.clip_time(**frame);
+        for hurtbox in hurtboxes.iter_mut() {
+            if let Some(offset) = hurtbox
+                .bone
+                .as_deref()
+                .and_then(|bone| rig.bone_offset(bone, time))
+            {
+                hurtbox.offset = offset;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track<T>(keys: impl IntoIterator<Item = (f32, T)>) -> Track<T> {
+        let (times, values) = keys.into_iter().unzip();
+        Track { times, values }
+    }
+
+    #[test]
+    fn tracks_interpolate_and_hold_their_ends() {
+        let track = track([(0.0, 0.0), (1.0, 10.0), (3.0, 30.0)]);
+        let sample = |time| track.sample(time, |a: f32, b, s| a + (b - a) * s);
+        assert_eq!(sample(-1.0), 0.0);
+        assert_eq!(sample(0.5), 5.0);
+        assert_eq!(sample(2.0), 20.0);
+        assert_eq!(sample(4.0), 30.0);
+    }
+
+    #[test]
+    fn bones_follow_their_animated_parents() {
+        let swing = track([
+            (0.0, Quat::IDENTITY),
+            (1.0, Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+        ]);
+        let rig = HurtboxRig {
+            duration: 1.0,
+            bones: HashMap::from([(
+                "Hand".to_string(),
+                vec![
+                    Joint {
+                        rest: Transform::from_xyz(0.0, 100.0, 0.0),
+                        rotation: Some(swing),
+                        ..default()
+                    },
+                    Joint {
+                        rest: Transform::from_xyz(50.0, 0.0, 0.0),
+                        ..default()
+                    },
+                ],
+            )]),
+        };
+        let at = |time| rig.bone_offset("Hand", time).unwrap();
+        assert!(at(0.0).abs_diff_eq(Vec3::new(50.0, 100.0, 0.0), 1e-3));
+        assert!(at(1.0).abs_diff_eq(Vec3::new(0.0, 150.0, 0.0), 1e-3));
+        assert_eq!(rig.bone_offset("Foot", 0.0), None);
+    }
+
+    #[test]
+    fn the_clip_loops_on_the_simulation_clock() {
+        let rig = HurtboxRig {
+            duration: 1.0,
+            ..default()
+        };
+        assert_eq!(rig.clip_time(0), 0.0);
+        assert!((rig.clip_time(30) - 0.5).abs() < 1e-5);
+        assert!((rig.clip_time(90) - 0.5).abs() < 1e-5);
+    }
+}
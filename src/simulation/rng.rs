@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+const SEED_MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+#[derive(Resource, Clone, Debug)]
+pub struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        // Xorshift never leaves a zero state, so the one seed that would produce it is moved.
+        let state = seed ^ SEED_MIX;
+        Self {
+            state: if state == 0 { SEED_MIX } else { state },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_seed_keeps_producing_numbers() {
+        for seed in [0, 3, SEED_MIX, u64::MAX] {
+            let mut rng = SimRng::new(seed);
+            assert!((0..4).any(|_| rng.next_u64() != 0), "seed {seed}");
+        }
+    }
+}
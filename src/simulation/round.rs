@@ -0,0 +1,287 @@
+use bevy::prelude::*;
+
+use super::combat::{AttackAnimationState, Combo, Hitbox, Juggle};
+use super::config::MatchConfig;
+use super::events::RoundEndEvent;
+use super::fighter::{Health, Player, Players, SpawnPoint, Velocity};
+use super::input::{FighterInput, PlayerInputMemory};
+use super::movement::{AirActions, MoveIntent, SlideState};
+use super::state::FighterState;
+use super::GameSet;
+
+const ROUND_INTRO_DURATION: u32 = 90;
+const KO_DURATION: u32 = 90;
+const ROUND_OVER_DURATION: u32 = 150;
+
+#[derive(States, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum RoundState {
+    #[default]
+    RoundIntro,
+    Fighting,
+    Ko,
+    RoundOver,
+    MatchOver,
+}
+
+#[derive(Resource, Default)]
+pub struct MatchScore {
+    pub round: u32,
+    pub wins: [u32; 2],
+    pub round_winner: Option<usize>,
+    pub time_over: bool,
+}
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RoundTimer(pub u32);
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RoundClock(pub Option<u32>);
+
+pub struct RoundPlugin;
+
+impl Plugin for RoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<RoundState>()
+            .insert_resource(MatchScore::default())
+            .insert_resource(RoundTimer::default())
+            .insert_resource(RoundClock::default())
+            .add_systems(OnEnter(RoundState::RoundIntro), start_round_system)
+            .add_systems(OnEnter(RoundState::Ko), enter_ko_system)
+            .add_systems(OnEnter(RoundState::RoundOver), enter_round_over_system)
+            .add_systems(
+                FixedUpdate,
+                (
+                    (round_clock_system, ko_detection_system)
+                        .chain()
+                        .run_if(in_state(RoundState::Fighting)),
+                    round_intro_system.run_if(in_state(RoundState::RoundIntro)),
+                    ko_system.run_if(in_state(RoundState::Ko)),
+                    round_over_system.run_if(in_state(RoundState::RoundOver)),
+                )
+                    .in_set(GameSet::Round),
+            )
+            // Settle the round inside the tick that decided it, so later ticks in the same
+            // frame already see the new state.
+            .add_systems(
+                FixedUpdate,
+                apply_state_transition::<RoundState>.after(GameSet::Round),
+            );
+    }
+}
+
+fn ko_detection_system(
+    players: Res<Players>,
+    mut fighters: Query<(&Health, &mut FighterState)>,
+    clock: Res<RoundClock>,
+    mut score: ResMut<MatchScore>,
+    mut next_state: ResMut<NextState<RoundState>>,
+    mut round_ends: EventWriter<RoundEndEvent>,
+) {
+    let Ok([(h1, mut s1), (h2, mut s2)]) =
+        fighters.get_many_mut([players.player1, players.player2])
+    else {
+        return;
+    };
+    let mut p1_lost = h1.current <= 0.0;
+    let mut p2_lost = h2.current <= 0.0;
+    let time_over = !p1_lost && !p2_lost && clock.0 == Some(0);
+    if time_over {
+        let r1 = h1.current / h1.max;
+        let r2 = h2.current / h2.max;
+        p1_lost = r1 <= r2;
+        p2_lost = r2 <= r1;
+    } else if !p1_lost && !p2_lost {
+        return;
+    }
+    score.time_over = time_over;
+    if h1.current <= 0.0 {
+        s1.transition(FighterState::Ko);
+    }
+    if h2.current <= 0.0 {
+        s2.transition(FighterState::Ko);
+    }
+    score.round_winner = match (p1_lost, p2_lost) {
+        (false, true) => Some(1),
+        (true, false) => Some(2),
+        _ => None,
+    };
+    if !p1_lost {
+        score.wins[0] += 1;
+    }
+    if !p2_lost {
+        score.wins[1] += 1;
+    }
+    if p1_lost && p2_lost {
+        score.wins[0] += 1;
+        score.wins[1] += 1;
+    }
+    round_ends.send(RoundEndEvent {
+        round: score.round,
+        winner: score.round_winner,
+        wins: score.wins,
+    });
+    next_state.set(RoundState::Ko);
+}
+
+fn start_round_system(
+    mut commands: Commands,
+    players: Res<Players>,
+    mut fighters: Query<(
+        &Player,
+        &SpawnPoint,
+        &mut Transform,
+        &mut Velocity,
+        &mut Health,
+        &mut FighterState,
+        &mut FighterInput,
+        &mut SlideState,
+        &mut MoveIntent,
+        &mut AirActions,
+        &mut AttackAnimationState,
+        &mut Juggle,
+        &mut Combo,
+    )>,
+    leftovers: Query<Entity, With<Hitbox>>,
+    mut input_memory: ResMut<PlayerInputMemory>,
+    mut score: ResMut<MatchScore>,
+    mut timer: ResMut<RoundTimer>,
+    mut clock: ResMut<RoundClock>,
+    config: Res<MatchConfig>,
+) {
+    for entity in leftovers.iter() {
+        commands.entity(entity).despawn();
+    }
+    for entity in [players.player1, players.player2] {
+        if let Ok((
+            player,
+            spawn,
+            mut transform,
+            mut vel,
+            mut health,
+            mut state,
+            mut input,
+            mut slide,
+            mut intent,
+            mut air,
+            mut anim,
+            mut juggle,
+            mut combo,
+        )) = fighters.get_mut(entity)
+        {
+            transform.translation = spawn.0;
+            **vel = Vec3::ZERO;
+            health.max = config.max_health(player.id);
+            health.current = health.max;
+            *state = FighterState::Idle;
+            *input = FighterInput::default();
+            *slide = SlideState::default();
+            *intent = MoveIntent::default();
+            *air = AirActions::default();
+            *anim = AttackAnimationState::default();
+            *juggle = Juggle::default();
+            *combo = Combo::default();
+        }
+    }
+    input_memory.last_press.clear();
+    score.round += 1;
+    score.round_winner = None;
+    score.time_over = false;
+    **timer = ROUND_INTRO_DURATION;
+    **clock = config.round_frames();
+}
+
+fn round_intro_system(
+    mut timer: ResMut<RoundTimer>,
+    mut next_state: ResMut<NextState<RoundState>>,
+) {
+    if tick_round_timer(&mut timer) {
+        next_state.set(RoundState::Fighting);
+    }
+}
+
+fn enter_ko_system(mut timer: ResMut<RoundTimer>) {
+    **timer = KO_DURATION;
+}
+
+fn ko_system(mut timer: ResMut<RoundTimer>, mut next_state: ResMut<NextState<RoundState>>) {
+    if tick_round_timer(&mut timer) {
+        next_state.set(RoundState::RoundOver);
+    }
+}
+
+fn enter_round_over_system(mut timer: ResMut<RoundTimer>) {
+    **timer = ROUND_OVER_DURATION;
+}
+
+fn round_over_system(
+    score: Res<MatchScore>,
+    config: Res<MatchConfig>,
+    mut timer: ResMut<RoundTimer>,
+    mut next_state: ResMut<NextState<RoundState>>,
+) {
+    if !tick_round_timer(&mut timer) {
+        return;
+    }
+    if score
+        .wins
+        .iter()
+        .any(|&wins| wins >= config.rounds_to_win())
+    {
+        next_state.set(RoundState::MatchOver);
+    } else {
+        next_state.set(RoundState::RoundIntro);
+    }
+}
+
+fn round_clock_system(mut clock: ResMut<RoundClock>) {
+    if let Some(frames) = clock.0.as_mut() {
+        *frames = frames.saturating_sub(1);
+    }
+}
+
+fn tick_round_timer(timer: &mut RoundTimer) -> bool {
+    **timer = timer.saturating_sub(1);
+    **timer == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::simulation::{SimulationPlugin, DT};
+
+    #[derive(Resource, Default)]
+    struct RoundEnds(Vec<[u32; 2]>);
+
+    fn record_round_ends(mut events: EventReader<RoundEndEvent>, mut ends: ResMut<RoundEnds>) {
+        ends.0.extend(events.iter().map(|end| end.wins));
+    }
+
+    #[test]
+    fn one_ko_wins_one_round_when_a_frame_runs_several_ticks() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SimulationPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                2. * DT,
+            )))
+            .init_resource::<RoundEnds>()
+            .add_systems(Update, record_round_ends);
+        app.finish();
+        app.cleanup();
+
+        while *app.world.resource::<State<RoundState>>().get() != RoundState::Fighting {
+            app.update();
+        }
+        let player2 = app.world.resource::<Players>().player2;
+        app.world.get_mut::<Health>(player2).unwrap().current = 0.0;
+        for _ in 0..ROUND_INTRO_DURATION + KO_DURATION + ROUND_OVER_DURATION {
+            app.update();
+        }
+
+        assert_eq!(app.world.resource::<RoundEnds>().0, [[1, 0]]);
+        let score = app.world.resource::<MatchScore>();
+        assert_eq!((score.round, score.wins), (2, [1, 0]));
+    }
+}
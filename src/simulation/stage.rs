@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::fighter::GameEntity;
+use super::ARENA_WIDTH;
+
+const CENTER_BLOCK_SIZE: f32 = 80.;
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    #[default]
+    Dojo,
+    Sunset,
+    Void,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Dojo, Stage::Sunset, Stage::Void];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Dojo => "dojo",
+            Stage::Sunset => "sunset",
+            Stage::Void => "void",
+        }
+    }
+
+    pub fn has_center_block(self) -> bool {
+        !matches!(self, Stage::Void)
+    }
+}
+
+/// Solid axis-aligned block of stage geometry that fighters cannot walk through.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct StageCollider {
+    pub half_extents: Vec3,
+}
+
+pub struct StagePlugin;
+
+impl Plugin for StagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_stage_colliders);
+    }
+}
+
+fn spawn_stage_colliders(mut commands: Commands, stage: Res<Stage>) {
+    if stage.has_center_block() {
+        commands.spawn((
+            TransformBundle::from_transform(Transform::from_xyz(
+                ARENA_WIDTH / 2.,
+                CENTER_BLOCK_SIZE / 2.,
+                0.,
+            )),
+            StageCollider {
+                half_extents: Vec3::splat(CENTER_BLOCK_SIZE / 2.),
+            },
+            GameEntity,
+        ));
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Stage::ALL
+            .into_iter()
+            .find(|stage| stage.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<_> = Stage::ALL.iter().map(|stage| stage.name()).collect();
+                format!("unknown stage {s}, expected one of {}", names.join(", "))
+            })
+    }
+}
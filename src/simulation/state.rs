@@ -0,0 +1,314 @@
+use bevy::prelude::*;
+
+use super::GameSet;
+
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FighterState {
+    #[default]
+    Idle,
+    Walk,
+    Dash {
+        frames_left: u32,
+    },
+    Startup {
+        frames_left: u32,
+    },
+    Active {
+        frames_left: u32,
+    },
+    Recovery {
+        frames_left: u32,
+    },
+    /// Crouching before leaving the ground for a jump.
+    Takeoff {
+        frames_left: u32,
+    },
+    Airborne,
+    /// Dashing through the air without falling.
+    AirDash {
+        frames_left: u32,
+    },
+    /// Recovering after touching down from a jump or an aerial attack.
+    Landing {
+        frames_left: u32,
+    },
+    Hitstun {
+        frames_left: u32,
+    },
+    Blockstun {
+        frames_left: u32,
+    },
+    /// Sent flying by a knockdown hit until reaching the floor.
+    Launched {
+        bounced: bool,
+    },
+    /// Hit while airborne; landing before the hitstun runs out is a knockdown.
+    Juggled {
+        frames_left: u32,
+    },
+    Knockdown {
+        frames_left: u32,
+    },
+    /// Tech roll on landing or rolling away on wake-up.
+    Roll {
+        frames_left: u32,
+    },
+    Ko,
+}
+
+impl FighterState {
+    pub fn can_transition(&self, next: &FighterState) -> bool {
+        use FighterState::*;
+        match (self, next) {
+            (Ko, _) => false,
+            (_, Ko) => true,
+            (
+                Idle | Walk,
+                Idle
+                | Walk
+                | Dash { .. }
+                | Startup { .. }
+                | Takeoff { .. }
+                | Airborne
+                | Landing { .. },
+            ) => true,
+            (Dash { .. }, Idle | Walk | Takeoff { .. } | Airborne) => true,
+            (Takeoff { .. }, Airborne) => true,
+            (Startup { .. }, Active { .. }) => true,
+            (Active { .. }, Recovery { .. }) => true,
+            (Recovery { .. }, Idle) => true,
+            (Startup { .. } | Active { .. } | Recovery { .. }, Landing { .. }) => true,
+            (Airborne, Idle | Startup { .. } | AirDash { .. } | Landing { .. }) => true,
+            (AirDash { .. }, Airborne | Landing { .. }) => true,
+            (Landing { .. }, Idle) => true,
+            (Hitstun { .. } | Blockstun { .. } | Juggled { .. } | Knockdown { .. }, Idle) => true,
+            (Launched { bounced: false }, Launched { bounced: true }) => true,
+            // Only a wake-up attack on the last frame down gets up into an attack.
+            (Knockdown { frames_left }, Startup { .. }) => *frames_left <= 1,
+            (Knockdown { .. }, Roll { .. }) => true,
+            (Knockdown { .. }, _) => false,
+            (Roll { .. }, Idle) => true,
+            (Blockstun { .. }, Blockstun { .. }) => true,
+            (
+                _,
+                Hitstun { .. }
+                | Blockstun { .. }
+                | Launched { bounced: false }
+                | Juggled { .. }
+                | Knockdown { .. },
+            ) => true,
+            _ => false,
+        }
+    }
+
+    pub fn transition(&mut self, next: FighterState) -> bool {
+        if !self.can_transition(&next) {
+            return false;
+        }
+        *self = next;
+        true
+    }
+
+    pub fn can_act(&self) -> bool {
+        matches!(self, FighterState::Idle | FighterState::Walk)
+    }
+
+    pub fn can_block(&self) -> bool {
+        matches!(
+            self,
+            FighterState::Idle | FighterState::Walk | FighterState::Blockstun { .. }
+        )
+    }
+
+    /// Whether a hit landing now would continue a combo.
+    pub fn is_stunned(&self) -> bool {
+        matches!(
+            self,
+            FighterState::Hitstun { .. }
+                | FighterState::Juggled { .. }
+                | FighterState::Launched { .. }
+        )
+    }
+
+    pub fn is_attacking(&self) -> bool {
+        matches!(
+            self,
+            FighterState::Startup { .. }
+                | FighterState::Active { .. }
+                | FighterState::Recovery { .. }
+        )
+    }
+
+    pub fn is_airborne(&self) -> bool {
+        matches!(self, FighterState::Airborne)
+    }
+
+    pub fn frames_left(&self) -> Option<u32> {
+        match *self {
+            FighterState::Dash { frames_left }
+            | FighterState::Startup { frames_left }
+            | FighterState::Active { frames_left }
+            | FighterState::Recovery { frames_left }
+            | FighterState::Takeoff { frames_left }
+            | FighterState::AirDash { frames_left }
+            | FighterState::Landing { frames_left }
+            | FighterState::Hitstun { frames_left }
+            | FighterState::Juggled { frames_left }
+            | FighterState::Blockstun { frames_left }
+            | FighterState::Knockdown { frames_left }
+            | FighterState::Roll { frames_left } => Some(frames_left),
+            _ => None,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.frames_left() == Some(0)
+    }
+
+    fn tick(&mut self) {
+        match self {
+            FighterState::Dash { frames_left }
+            | FighterState::Startup { frames_left }
+            | FighterState::Active { frames_left }
+            | FighterState::Recovery { frames_left }
+            | FighterState::Takeoff { frames_left }
+            | FighterState::AirDash { frames_left }
+            | FighterState::Landing { frames_left }
+            | FighterState::Hitstun { frames_left }
+            | FighterState::Juggled { frames_left }
+            | FighterState::Blockstun { frames_left }
+            | FighterState::Knockdown { frames_left }
+            | FighterState::Roll { frames_left } => {
+                *frames_left = frames_left.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub struct FighterStatePlugin;
+
+impl Plugin for FighterStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (tick_fighter_state_system, expire_states_system)
+                .chain()
+                .in_set(GameSet::Movement),
+        );
+    }
+}
+
+fn tick_fighter_state_system(mut query: Query<&mut FighterState>) {
+    for mut state in query.iter_mut() {
+        state.tick();
+    }
+}
+
+fn expire_states_system(mut query: Query<&mut FighterState>) {
+    for mut state in query.iter_mut() {
+        if state.expired() && !state.is_attacking() {
+            // An air dash runs out in mid-air, where the fighter keeps falling.
+            let next = match *state {
+                FighterState::AirDash { .. } => FighterState::Airborne,
+                _ => FighterState::Idle,
+            };
+            state.transition(next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{IntoSystemConfigs, Schedule, World};
+
+    use super::FighterState::*;
+    use super::{expire_states_system, tick_fighter_state_system, FighterState};
+
+    fn run_movement(state: FighterState) -> FighterState {
+        let mut world = World::new();
+        let fighter = world.spawn(state).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((tick_fighter_state_system, expire_states_system).chain());
+        schedule.run(&mut world);
+        *world.get::<FighterState>(fighter).unwrap()
+    }
+
+    #[test]
+    fn attacks_run_through_their_phases() {
+        assert!(Idle.can_transition(&Startup { frames_left: 5 }));
+        assert!(Startup { frames_left: 0 }.can_transition(&Active { frames_left: 3 }));
+        assert!(Active { frames_left: 0 }.can_transition(&Recovery { frames_left: 8 }));
+        assert!(Recovery { frames_left: 0 }.can_transition(&Idle));
+        assert!(!Startup { frames_left: 5 }.can_transition(&Idle));
+        assert!(!Active { frames_left: 3 }.can_transition(&Startup { frames_left: 5 }));
+    }
+
+    #[test]
+    fn hits_interrupt_anything_but_ko() {
+        for state in [
+            Idle,
+            Walk,
+            Startup { frames_left: 5 },
+            Recovery { frames_left: 5 },
+            Airborne,
+            AirDash { frames_left: 5 },
+            Hitstun { frames_left: 5 },
+        ] {
+            assert!(
+                state.can_transition(&Hitstun { frames_left: 10 }),
+                "{state:?}"
+            );
+            assert!(state.can_transition(&Ko), "{state:?}");
+        }
+        assert!(!Ko.can_transition(&Hitstun { frames_left: 10 }));
+        assert!(!Ko.can_transition(&Idle));
+    }
+
+    #[test]
+    fn stunned_fighters_cannot_act() {
+        assert!(!Hitstun { frames_left: 5 }.can_transition(&Startup { frames_left: 5 }));
+        assert!(!Blockstun { frames_left: 5 }.can_transition(&Walk));
+        assert!(!Juggled { frames_left: 5 }.can_transition(&AirDash { frames_left: 5 }));
+        assert!(!Launched { bounced: false }.can_transition(&Idle));
+        assert!(Launched { bounced: false }.can_transition(&Launched { bounced: true }));
+        assert!(!Launched { bounced: true }.can_transition(&Launched { bounced: true }));
+    }
+
+    #[test]
+    fn knockdown_only_gets_up_into_an_attack_on_the_last_frame() {
+        assert!(!Knockdown { frames_left: 20 }.can_transition(&Startup { frames_left: 5 }));
+        assert!(Knockdown { frames_left: 1 }.can_transition(&Startup { frames_left: 5 }));
+        assert!(Knockdown { frames_left: 20 }.can_transition(&Roll { frames_left: 10 }));
+        assert!(!Knockdown { frames_left: 20 }.can_transition(&Walk));
+        assert!(!Knockdown { frames_left: 20 }.can_transition(&Takeoff { frames_left: 3 }));
+    }
+
+    #[test]
+    fn air_dash_cannot_end_on_the_ground_without_landing() {
+        assert!(Airborne.can_transition(&AirDash { frames_left: 10 }));
+        assert!(!AirDash { frames_left: 10 }.can_transition(&Idle));
+        assert!(AirDash { frames_left: 10 }.can_transition(&Landing { frames_left: 4 }));
+    }
+
+    #[test]
+    fn air_dash_runs_its_full_length_then_falls() {
+        let mut state = AirDash { frames_left: 3 };
+        for _ in 0..2 {
+            state = run_movement(state);
+            assert!(matches!(state, AirDash { .. }), "{state:?}");
+        }
+        assert_eq!(run_movement(state), Airborne);
+    }
+
+    #[test]
+    fn expired_states_return_to_idle_except_attacks() {
+        assert_eq!(run_movement(Hitstun { frames_left: 1 }), Idle);
+        assert_eq!(run_movement(Knockdown { frames_left: 1 }), Idle);
+        // Attack phases are advanced by the attack animation instead.
+        assert_eq!(
+            run_movement(Recovery { frames_left: 1 }),
+            Recovery { frames_left: 0 }
+        );
+    }
+}